@@ -85,22 +85,24 @@ impl App {
     }
 
     fn view(&self) -> Element<'_, Message> {
-        Column::new()
-            .push(
-                Container::new(
-                    VideoPlayer::new(&self.video)
-                        .width(iced::Length::Fill)
-                        .height(iced::Length::Fill)
-                        .content_fit(iced::ContentFit::Contain)
-                        .on_end_of_stream(Message::EndOfStream)
-                        .on_new_frame(Message::NewFrame),
-                )
-                .align_x(iced::Alignment::Center)
-                .align_y(iced::Alignment::Center)
-                .width(iced::Length::Fill)
-                .height(iced::Length::Fill),
+        let mut column = Column::new().push(
+            Container::new(
+                VideoPlayer::new(&self.video)
+                    .width(iced::Length::Fill)
+                    .height(iced::Length::Fill)
+                    .content_fit(iced::ContentFit::Contain)
+                    .on_end_of_stream(Message::EndOfStream)
+                    .on_new_frame(Message::NewFrame),
             )
-            .push(
+            .align_x(iced::Alignment::Center)
+            .align_y(iced::Alignment::Center)
+            .width(iced::Length::Fill)
+            .height(iced::Length::Fill),
+        );
+
+        // live sources have no duration to seek through
+        if !self.video.is_live() {
+            column = column.push(
                 Container::new(
                     Slider::new(
                         0.0..=self.video.duration().as_secs_f64(),
@@ -111,7 +113,10 @@ impl App {
                     .on_release(Message::SeekRelease),
                 )
                 .padding(iced::Padding::new(5.0).left(10.0).right(10.0)),
-            )
+            );
+        }
+
+        column
             .push(
                 Row::new()
                     .spacing(5)
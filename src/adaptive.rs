@@ -0,0 +1,212 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+/// A quality variant of an adaptive (HLS/DASH) stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quality {
+    /// The advertised bitrate of the variant in bits per second.
+    pub bitrate: u64,
+    /// The advertised resolution of the variant as `(width, height)`, if any.
+    pub resolution: Option<(u32, u32)>,
+}
+
+impl std::fmt::Display for Quality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.resolution {
+            Some((_, height)) => write!(f, "{}p ({} kbps)", height, self.bitrate / 1000),
+            None => write!(f, "{} kbps", self.bitrate / 1000),
+        }
+    }
+}
+
+/// Returns whether the element is an adaptive streaming demuxer.
+pub(crate) fn is_adaptive_demuxer(element: &gst::Element) -> bool {
+    element.factory().is_some_and(|factory| {
+        let name = factory.name();
+        name.starts_with("hlsdemux")
+            || name.starts_with("dashdemux")
+            || name.starts_with("mssdemux")
+    })
+}
+
+/// Limits the bitrate adaptive demuxers may pick. A `kbps` of zero lifts the limit.
+pub(crate) fn set_connection_speed(pipeline: &gst::Pipeline, kbps: u64) {
+    // playbin only forwards this to sources created after the change
+    pipeline.set_property("connection-speed", kbps);
+
+    for element in pipeline.iterate_recurse().into_iter().flatten() {
+        if !is_adaptive_demuxer(&element) {
+            continue;
+        }
+        if let Some(pspec) = element.find_property("connection-speed")
+            && pspec.value_type() == u32::static_type()
+        {
+            element.set_property("connection-speed", kbps.min(u32::MAX as u64) as u32);
+        }
+    }
+}
+
+/// Parses the variants advertised by an HLS master playlist or a DASH MPD,
+/// sorted from highest to lowest bitrate.
+pub(crate) fn parse_manifest(manifest: &str) -> Vec<Quality> {
+    let mut qualities: Vec<_> = if manifest.trim_start().starts_with("#EXTM3U") {
+        manifest
+            .lines()
+            .filter_map(|line| line.strip_prefix("#EXT-X-STREAM-INF:"))
+            .filter_map(parse_hls_variant)
+            .collect()
+    } else {
+        manifest
+            .split("<Representation")
+            .skip(1)
+            .filter_map(|tag| parse_dash_representation(tag.split('>').next()?))
+            .collect()
+    };
+
+    qualities.sort_by(|a, b| b.bitrate.cmp(&a.bitrate));
+    qualities.dedup();
+    qualities
+}
+
+fn parse_hls_variant(attributes: &str) -> Option<Quality> {
+    let mut bitrate = None;
+    let mut resolution = None;
+
+    // attribute values may be quoted and contain commas (e.g., CODECS="avc1,mp4a")
+    let mut in_quotes = false;
+    let attributes = attributes.split(|c| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ',' && !in_quotes
+    });
+
+    for attribute in attributes {
+        match attribute.trim().split_once('=') {
+            Some(("BANDWIDTH", value)) => bitrate = value.parse().ok(),
+            Some(("RESOLUTION", value)) => {
+                resolution = value
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)));
+            }
+            _ => {}
+        }
+    }
+
+    Some(Quality {
+        bitrate: bitrate?,
+        resolution,
+    })
+}
+
+fn parse_dash_representation(tag: &str) -> Option<Quality> {
+    let attribute = |name: &str| -> Option<u64> {
+        let pattern = format!("{name}=\"");
+        let (start, _) = tag
+            .match_indices(&pattern)
+            .find(|(i, _)| tag[..*i].ends_with(char::is_whitespace))?;
+        let value = &tag[start + pattern.len()..];
+        value[..value.find('"')?].parse().ok()
+    };
+
+    let resolution = attribute("width")
+        .zip(attribute("height"))
+        .map(|(width, height)| (width as u32, height as u32));
+
+    Some(Quality {
+        bitrate: attribute("bandwidth")?,
+        resolution,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hls_variants() {
+        let manifest = "#EXTM3U
+#EXT-X-VERSION:3
+#EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640x360,CODECS=\"avc1.4d401e,mp4a.40.2\"
+360p.m3u8
+#EXT-X-STREAM-INF:AVERAGE-BANDWIDTH=2000000,BANDWIDTH=2500000,RESOLUTION=1280x720
+720p.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\"
+audio.m3u8
+";
+        assert_eq!(
+            parse_manifest(manifest),
+            [
+                Quality {
+                    bitrate: 2_500_000,
+                    resolution: Some((1280, 720)),
+                },
+                Quality {
+                    bitrate: 800_000,
+                    resolution: Some((640, 360)),
+                },
+                Quality {
+                    bitrate: 64_000,
+                    resolution: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_malformed_hls_variants() {
+        let manifest = "#EXTM3U
+#EXT-X-STREAM-INF:RESOLUTION=640x360
+missing-bandwidth.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=fast
+invalid-bandwidth.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640
+invalid-resolution.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=800000
+duplicate.m3u8
+";
+        assert_eq!(
+            parse_manifest(manifest),
+            [Quality {
+                bitrate: 800_000,
+                resolution: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_dash_representations() {
+        let manifest = r#"<?xml version="1.0"?>
+<MPD><Period><AdaptationSet maxWidth="1920">
+<Representation id="1" bandwidth="1000000" width="1280" height="720" codecs="avc1"/>
+<Representation id="2" bandwidth="3000000" width="1920" height="1080">
+</Representation>
+<Representation id="3" mimeType="video/mp4" width="640" height="360"/>
+<Representation id="4" bandwidth="128000" audioSamplingRate="48000"/>
+<Representation id="5" bandwidth="many"/>
+</AdaptationSet></Period></MPD>"#;
+        assert_eq!(
+            parse_manifest(manifest),
+            [
+                Quality {
+                    bitrate: 3_000_000,
+                    resolution: Some((1920, 1080)),
+                },
+                Quality {
+                    bitrate: 1_000_000,
+                    resolution: Some((1280, 720)),
+                },
+                Quality {
+                    bitrate: 128_000,
+                    resolution: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_empty_manifests() {
+        assert!(parse_manifest("").is_empty());
+        assert!(parse_manifest("#EXTM3U\n#EXT-X-TARGETDURATION:10\n").is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+use crate::Error;
+use crate::adaptive;
+use crate::level::SilenceDetector;
+use crate::video::{self, FrameFormat, LateFramePolicy, Position, Video, VideoFilters};
+use gstreamer as gst;
+use gstreamer_app as gst_app;
+use gstreamer_app::prelude::*;
+use iced::futures::channel::oneshot;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Configures a [`Video`] before its pipeline starts playing, see [`Video::builder`].
+#[derive(Debug, Clone)]
+pub struct VideoBuilder {
+    uri: url::Url,
+    paused: bool,
+    start_at: Option<Position>,
+    volume: f64,
+    muted: bool,
+    looping: bool,
+    subtitle_url: Option<url::Url>,
+    text_offset: i64,
+    preferred_languages: Vec<String>,
+    prefer_sdh: bool,
+    video_filters: bool,
+    output_size: Option<(u32, u32)>,
+    output_format: FrameFormat,
+    require_approval: bool,
+    late_frame_policy: LateFramePolicy,
+    max_lateness: Option<Duration>,
+    visualization: Option<String>,
+    audio_levels: Option<Duration>,
+    silence_detection: Option<(f64, Duration)>,
+    playbin3: bool,
+}
+
+impl VideoBuilder {
+    /// Creates a new builder for a video which loads from `uri`.
+    pub fn new(uri: &url::Url) -> Self {
+        VideoBuilder {
+            uri: uri.clone(),
+            paused: false,
+            start_at: None,
+            volume: 1.0,
+            muted: false,
+            looping: false,
+            subtitle_url: None,
+            text_offset: 0,
+            preferred_languages: Vec::new(),
+            prefer_sdh: false,
+            video_filters: true,
+            output_size: None,
+            output_format: FrameFormat::Nv12,
+            require_approval: false,
+            late_frame_policy: LateFramePolicy::DropOldest,
+            max_lateness: None,
+            visualization: None,
+            audio_levels: None,
+            silence_detection: None,
+            playbin3: false,
+        }
+    }
+
+    /// Sets if the video starts paused. The first frame is still shown.
+    pub fn paused(self, paused: bool) -> Self {
+        VideoBuilder { paused, ..self }
+    }
+
+    /// Sets the position playback starts at.
+    pub fn start_at(self, position: impl Into<Position>) -> Self {
+        VideoBuilder {
+            start_at: Some(position.into()),
+            ..self
+        }
+    }
+
+    /// Sets the initial volume multiplier of the audio, see [`Video::set_volume`].
+    pub fn volume(self, volume: f64) -> Self {
+        VideoBuilder { volume, ..self }
+    }
+
+    /// Sets if the audio is initially muted.
+    pub fn muted(self, muted: bool) -> Self {
+        VideoBuilder { muted, ..self }
+    }
+
+    /// Sets if the media will loop.
+    pub fn looping(self, looping: bool) -> Self {
+        VideoBuilder { looping, ..self }
+    }
+
+    /// Sets an external subtitle file to display.
+    pub fn subtitle_url(self, url: &url::Url) -> Self {
+        VideoBuilder {
+            subtitle_url: Some(url.clone()),
+            ..self
+        }
+    }
+
+    /// Sets the synchronisation offset between the text and video streams in
+    /// nano seconds, see [`Video::set_text_offset`].
+    pub fn text_offset(self, text_offset: i64) -> Self {
+        VideoBuilder {
+            text_offset,
+            ..self
+        }
+    }
+
+    /// Sets the preferred languages of the audio and subtitle tracks, see
+    /// [`Video::set_preferred_languages`].
+    pub fn preferred_languages(self, languages: &[&str]) -> Self {
+        VideoBuilder {
+            preferred_languages: languages
+                .iter()
+                .map(|&language| language.to_owned())
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Sets whether SDH subtitles are preferred, see [`Video::set_prefer_sdh`].
+    pub fn prefer_sdh(self, prefer_sdh: bool) -> Self {
+        VideoBuilder { prefer_sdh, ..self }
+    }
+
+    /// Sets if the balance and gamma [`VideoFilters`] are inserted into the
+    /// pipeline. Enabled by default; disabling them saves some processing.
+    pub fn video_filters(self, video_filters: bool) -> Self {
+        VideoBuilder {
+            video_filters,
+            ..self
+        }
+    }
+
+    /// Sets the resolution frames are scaled to before being displayed, rather
+    /// than the resolution of the media.
+    pub fn output_size(self, width: u32, height: u32) -> Self {
+        VideoBuilder {
+            output_size: Some((width, height)),
+            ..self
+        }
+    }
+
+    /// Sets the pixel format frames are decoded to, e.g., RGBA for frame
+    /// processors which work on RGB. NV12 by default, which is cheaper to
+    /// convert to and upload.
+    pub fn output_format(self, output_format: FrameFormat) -> Self {
+        VideoBuilder {
+            output_format,
+            ..self
+        }
+    }
+
+    /// Sets if playback is blocked until [`Video::approve`] is called. The video
+    /// starts paused and hidden, but prerolled, see [`Video::require_approval`].
+    pub fn require_approval(self, require_approval: bool) -> Self {
+        VideoBuilder {
+            require_approval,
+            ..self
+        }
+    }
+
+    /// Sets what happens to decoded frames when the UI falls behind, see
+    /// [`Video::set_late_frame_policy`].
+    pub fn late_frame_policy(self, late_frame_policy: LateFramePolicy) -> Self {
+        VideoBuilder {
+            late_frame_policy,
+            ..self
+        }
+    }
+
+    /// Sets how late a frame may be before it is dropped, see
+    /// [`Video::set_max_lateness`].
+    pub fn max_lateness(self, max_lateness: Option<Duration>) -> Self {
+        VideoBuilder {
+            max_lateness,
+            ..self
+        }
+    }
+
+    /// Sets a visualizer element (e.g., `goom` or `wavescope`) which renders
+    /// audio-only media, rather than showing nothing. Media with a video
+    /// stream is unaffected.
+    pub fn visualization(self, visualizer: &str) -> Self {
+        VideoBuilder {
+            visualization: Some(visualizer.to_owned()),
+            ..self
+        }
+    }
+
+    /// Measures the peak and RMS levels of the audio every `interval`, e.g.,
+    /// to drive a VU meter, see [`VideoPlayer::on_audio_level`](crate::VideoPlayer::on_audio_level).
+    pub fn audio_levels(self, interval: Duration) -> Self {
+        VideoBuilder {
+            audio_levels: Some(interval),
+            ..self
+        }
+    }
+
+    /// Detects when the audio stays below `threshold` (in decibels, e.g., `-50.0`)
+    /// for at least `min_duration`, see [`VideoPlayer::on_silence_start`](crate::VideoPlayer::on_silence_start).
+    ///
+    /// Measures the audio levels every 100 ms, unless set with [`VideoBuilder::audio_levels`].
+    pub fn silence_detection(self, threshold: f64, min_duration: Duration) -> Self {
+        VideoBuilder {
+            silence_detection: Some((threshold, min_duration)),
+            ..self
+        }
+    }
+
+    /// Sets if the media is played by `playbin3` rather than `playbin`.
+    ///
+    /// `playbin3` selects tracks from the streams of the media as a whole,
+    /// switches tracks without flushing, and handles adaptive streams better.
+    /// It requires GStreamer 1.22 or newer.
+    pub fn playbin3(self, playbin3: bool) -> Self {
+        VideoBuilder { playbin3, ..self }
+    }
+
+    /// Builds the [`Video`] on a separate thread, so that waiting for the
+    /// pipeline to preroll does not block the caller (e.g., the GUI thread).
+    ///
+    /// The returned future can be run with `iced::Task::perform`.
+    pub async fn build_async(self) -> Result<Video, Error> {
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(self.build());
+        });
+        receiver.await.map_err(|_| Error::Thread)?
+    }
+
+    /// Builds the [`Video`], prerolling its pipeline.
+    ///
+    /// This blocks for up to several seconds while the media is probed, see
+    /// [`VideoBuilder::build_async`].
+    pub fn build(self) -> Result<Video, Error> {
+        gst::init()?;
+
+        // the pixel aspect ratio is left as is, it is accounted for when drawing
+        let mut caps = format!("video/x-raw,format={}", self.output_format.caps_name());
+        if let Some((width, height)) = self.output_size {
+            caps.push_str(&format!(",width={width},height={height}"));
+        }
+
+        let mut audio_filter =
+            String::from("pitch name=pitch ! audioconvert ! capsfilter name=downmix");
+        let level_interval = self
+            .audio_levels
+            .or(self.silence_detection.map(|_| Duration::from_millis(100)));
+        if let Some(interval) = level_interval {
+            // measures the audio as it is output, after downmixing
+            audio_filter.push_str(&format!(
+                " ! level name=level post-messages=true interval={}",
+                interval.as_nanos()
+            ));
+        }
+
+        let mut pipeline = format!(
+            "{} uri=\"{}\" text-sink=\"appsink name=iced_text sync=true drop=true\" video-sink=\"videoscale ! videoconvert ! appsink name=iced_video drop=true caps={}\" audio-filter=\"{}\"",
+            if self.playbin3 { "playbin3" } else { "playbin" },
+            self.uri.as_str(),
+            caps,
+            audio_filter
+        );
+        if self.video_filters {
+            pipeline.push_str(" video-filter=\"videobalance name=balance ! gamma name=gamma\"");
+        }
+        let pipeline = gst::parse::launch(pipeline.as_ref())?
+            .downcast::<gst::Pipeline>()
+            .map_err(|_| Error::Cast("gst::Pipeline"))?;
+
+        pipeline.set_property("volume", self.volume);
+        pipeline.set_property("mute", self.muted);
+        pipeline.set_property("text-offset", -self.text_offset);
+        if let Some(url) = &self.subtitle_url {
+            pipeline.set_property("suburi", url.as_str());
+        }
+        if let Some(visualizer) = &self.visualization {
+            let visualizer = gst::ElementFactory::make(visualizer).build()?;
+            pipeline.set_property("vis-plugin", &visualizer);
+            video::set_flag(&pipeline, "vis", true);
+        }
+
+        // capture the manifest of adaptive streams as it flows into the demuxer
+        let manifest = Arc::new(Mutex::new(Vec::new()));
+        let manifest_ref = Arc::clone(&manifest);
+        pipeline.connect("element-setup", false, move |args| {
+            let element = args[1].get::<gst::Element>().ok()?;
+            if !adaptive::is_adaptive_demuxer(&element) {
+                return None;
+            }
+            manifest_ref.lock().ok()?.clear();
+            let manifest_ref = Arc::clone(&manifest_ref);
+            element
+                .static_pad("sink")?
+                .add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+                    if let Some(gst::PadProbeData::Buffer(ref buffer)) = info.data
+                        && let Ok(map) = buffer.map_readable()
+                        && let Ok(mut manifest) = manifest_ref.lock()
+                    {
+                        manifest.extend_from_slice(map.as_slice());
+                    }
+                    gst::PadProbeReturn::Ok
+                });
+            None
+        });
+
+        let video_sink: gst::Element = pipeline.property("video-sink");
+        let pad = video_sink.pads().first().cloned().unwrap();
+        let pad = pad.dynamic_cast::<gst::GhostPad>().unwrap();
+        let bin = pad
+            .parent_element()
+            .unwrap()
+            .downcast::<gst::Bin>()
+            .unwrap();
+        let video_sink = bin.by_name("iced_video").unwrap();
+        let video_sink = video_sink.downcast::<gst_app::AppSink>().unwrap();
+        // tells upstream elements when frames are late, so that decoders skip
+        // decoding frames which would be dropped anyway
+        video_sink.set_property("qos", true);
+        video_sink.set_max_lateness(
+            self.max_lateness
+                .map_or(-1, |lateness| lateness.as_nanos() as i64),
+        );
+
+        let text_sink: gst::Element = pipeline.property("text-sink");
+        let text_sink = text_sink.downcast::<gst_app::AppSink>().unwrap();
+
+        let filters = if self.video_filters {
+            let filter: gst::Element = pipeline.property("video-filter");
+            let pad = filter.pads().first().cloned().unwrap();
+            let pad = pad.dynamic_cast::<gst::GhostPad>().unwrap();
+            let bin = pad
+                .parent_element()
+                .unwrap()
+                .downcast::<gst::Bin>()
+                .unwrap();
+            let balance = bin.by_name("balance").unwrap();
+
+            let gamma: gst::Element = bin.by_name("gamma").unwrap();
+
+            VideoFilters::all(balance, gamma)
+        } else {
+            VideoFilters::none()
+        };
+
+        let mut output = Video::from_gst_pipeline_with(
+            pipeline,
+            video_sink,
+            Some(text_sink),
+            self.paused || self.require_approval,
+            self.start_at,
+        )?;
+        output.set_video_filters(filters);
+
+        {
+            let mut inner = output.get_mut();
+            inner.manifest = manifest;
+            inner.set_looping(self.looping);
+            inner.silence = self
+                .silence_detection
+                .map(|(threshold, min_duration)| SilenceDetector::new(threshold, min_duration));
+            inner.preferred_languages = self.preferred_languages;
+            inner.prefer_sdh = self.prefer_sdh;
+            inner.select_preferred_tracks();
+            self.late_frame_policy.store(&inner.late_frame_policy);
+            inner
+                .awaiting_approval
+                .store(self.require_approval, Ordering::SeqCst);
+        }
+
+        Ok(output)
+    }
+}
@@ -0,0 +1,221 @@
+use crate::Error;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// How much of the start and end of a file is hashed to identify it.
+const HASHED_BYTES: u64 = 64 * 1024;
+
+/// How full the cache is left once it outgrew its limit, see [`ThumbnailCache::evict`].
+const EVICTED_FILL: f64 = 0.9;
+
+/// An on-disk cache of thumbnails, so that reopening the same media doesn't
+/// decode its thumbnails again, see [`Video::set_thumbnail_cache`](crate::Video::set_thumbnail_cache).
+///
+/// Thumbnails are keyed by a hash of the media and their position and size.
+/// Local files are identified by their length, modification time and the
+/// start and end of their content, so files modified since are unlikely to
+/// hit stale thumbnails. Once the cache outgrows its limit, the least recently
+/// used thumbnails are evicted.
+#[derive(Debug, Clone)]
+pub struct ThumbnailCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    /// The bytes stored in `dir`, counted once on the first store.
+    used_bytes: Arc<Mutex<Option<u64>>>,
+}
+
+impl ThumbnailCache {
+    /// Opens the cache in `dir`, creating the directory if needed. Holds up to 256 MiB by default.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, Error> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(ThumbnailCache {
+            dir,
+            max_bytes: 256 * 1024 * 1024,
+            used_bytes: Arc::default(),
+        })
+    }
+
+    /// Sets how many bytes of thumbnails are kept before the least recently used are evicted.
+    pub fn max_bytes(self, max_bytes: u64) -> Self {
+        ThumbnailCache { max_bytes, ..self }
+    }
+
+    /// Removes the cached thumbnails of the media at `uri`.
+    pub fn invalidate(&self, uri: &url::Url) -> Result<(), Error> {
+        let dir = self.dir.join(media_key(uri.as_str()));
+        self.forget_used_bytes();
+        match fs::remove_dir_all(dir) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes every cached thumbnail.
+    pub fn clear(&self) -> Result<(), Error> {
+        self.forget_used_bytes();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                fs::remove_dir_all(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the RGBA data of a thumbnail, if cached.
+    pub(crate) fn load(
+        &self,
+        media: &str,
+        position: Duration,
+        size: iced::Size<u32>,
+    ) -> Option<Vec<u8>> {
+        let path = self.path(media, position, size);
+        let data = fs::read(&path).ok()?;
+        if data.len() != (size.width * size.height * 4) as usize {
+            return None;
+        }
+        // the modification time orders entries for eviction
+        let _ = fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()));
+        Some(data)
+    }
+
+    /// Stores the RGBA data of a thumbnail, evicting others if the cache is full.
+    pub(crate) fn store(
+        &self,
+        media: &str,
+        position: Duration,
+        size: iced::Size<u32>,
+        rgba: &[u8],
+    ) {
+        let path = self.path(media, position, size);
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, rgba));
+        if let Err(err) = result {
+            log::warn!("failed to cache thumbnail at {}: {err}", path.display());
+            return;
+        }
+
+        let Ok(mut used_bytes) = self.used_bytes.lock() else {
+            return;
+        };
+        // overwritten thumbnails are counted twice until the next eviction recounts
+        let used = used_bytes.get_or_insert_with(|| self.entries().map(|(_, len, _)| len).sum());
+        *used += rgba.len() as u64;
+        if *used > self.max_bytes {
+            *used = self.evict();
+        }
+    }
+
+    fn forget_used_bytes(&self) {
+        if let Ok(mut used_bytes) = self.used_bytes.lock() {
+            *used_bytes = None;
+        }
+    }
+
+    fn path(&self, media: &str, position: Duration, size: iced::Size<u32>) -> PathBuf {
+        self.dir.join(media).join(format!(
+            "{}_{}x{}.rgba",
+            position.as_nanos(),
+            size.width,
+            size.height
+        ))
+    }
+
+    /// Lists the modification time, length and path of every cached thumbnail.
+    fn entries(&self) -> impl Iterator<Item = (SystemTime, u64, PathBuf)> {
+        fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|media| fs::read_dir(media.path()).ok())
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+            })
+    }
+
+    /// Evicts the least recently used thumbnails until the cache is filled to
+    /// [`EVICTED_FILL`] of its limit, so that the cache is walked once per batch
+    /// of stores rather than on each. Returns the bytes left.
+    fn evict(&self) -> u64 {
+        let mut entries: Vec<_> = self.entries().collect();
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        if total <= self.max_bytes {
+            return total;
+        }
+
+        let target = (self.max_bytes as f64 * EVICTED_FILL) as u64;
+        entries.sort_by_key(|(modified, _, _)| *modified);
+        for (_, len, path) in entries {
+            if total <= target {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total -= len;
+            }
+        }
+        total
+    }
+}
+
+/// Identifies media by the content of local files, or by the URI otherwise.
+pub(crate) fn media_key(uri: &str) -> String {
+    let file = url::Url::parse(uri)
+        .ok()
+        .filter(|url| url.scheme() == "file")
+        .and_then(|url| url.to_file_path().ok());
+    let hash = match file.and_then(|path| hash_file(&path).ok()) {
+        Some(hash) => hash,
+        None => fnv1a(FNV_OFFSET, uri.as_bytes()),
+    };
+    format!("{hash:016x}")
+}
+
+/// Hashes the length, modification time, start and end of a file, which is
+/// enough to tell media apart without reading all of it.
+fn hash_file(path: &Path) -> std::io::Result<u64> {
+    let mut file = fs::File::open(path)?;
+    let metadata = file.metadata()?;
+    let len = metadata.len();
+    let mut hash = fnv1a(FNV_OFFSET, &len.to_le_bytes());
+    // catches edits in the middle of the file, which the content hash skips
+    if let Ok(modified) = metadata.modified()
+        && let Ok(since_epoch) = modified.duration_since(SystemTime::UNIX_EPOCH)
+    {
+        hash = fnv1a(hash, &since_epoch.as_nanos().to_le_bytes());
+    }
+
+    let mut buffer = Vec::new();
+    (&mut file).take(HASHED_BYTES).read_to_end(&mut buffer)?;
+    hash = fnv1a(hash, &buffer);
+
+    if len > HASHED_BYTES {
+        buffer.clear();
+        file.seek(SeekFrom::Start(
+            len.saturating_sub(HASHED_BYTES).max(HASHED_BYTES),
+        ))?;
+        file.read_to_end(&mut buffer)?;
+        hash = fnv1a(hash, &buffer);
+    }
+    Ok(hash)
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across Rust versions.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,89 @@
+use crate::Error;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+
+/// A camera or other video capture device, see [`Video::from_capture_device`](crate::Video::from_capture_device).
+#[derive(Debug, Clone)]
+pub struct CaptureDevice {
+    /// The human-readable name of the device.
+    pub name: String,
+    pub(crate) device: gst::Device,
+}
+
+impl CaptureDevice {
+    /// Lists the video capture devices currently connected.
+    pub fn list() -> Result<Vec<CaptureDevice>, Error> {
+        gst::init()?;
+
+        let monitor = gst::DeviceMonitor::new();
+        monitor.add_filter(
+            Some("Video/Source"),
+            Some(&gst::Caps::new_empty_simple("video/x-raw")),
+        );
+        monitor.start()?;
+        let devices = monitor
+            .devices()
+            .into_iter()
+            .map(|device| CaptureDevice {
+                name: device.display_name().to_string(),
+                device,
+            })
+            .collect();
+        monitor.stop();
+
+        Ok(devices)
+    }
+
+    /// The underlying GStreamer device, e.g., to inspect its supported caps.
+    pub fn device(&self) -> &gst::Device {
+        &self.device
+    }
+}
+
+/// The rate at which the screen is captured, see [`screen_source`].
+const SCREEN_FRAMERATE: u32 = 30;
+
+/// Creates the platform's screen capture source for the `monitor`th display.
+pub(crate) fn screen_source(monitor: u32) -> Result<gst::Element, Error> {
+    // Direct3D captures stay in GPU memory until downloaded
+    #[cfg(target_os = "windows")]
+    let source = format!("d3d11screencapturesrc monitor-index={monitor} ! d3d11download");
+    #[cfg(target_os = "macos")]
+    let source = format!("avfvideosrc capture-screen=true device-index={monitor}");
+    // X11 only captures the whole screen, spanning all monitors
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let source = {
+        let _ = monitor;
+        String::from("ximagesrc use-damage=false")
+    };
+
+    let bin = gst::parse::bin_from_description(
+        &format!("{source} ! video/x-raw,framerate={SCREEN_FRAMERATE}/1"),
+        true,
+    )?;
+    Ok(bin.upcast())
+}
+
+/// Builds a pipeline which converts the raw video of `source` for the video sink.
+pub(crate) fn pipeline(source: gst::Element) -> Result<(gst::Pipeline, gst_app::AppSink), Error> {
+    let pipeline = gst::Pipeline::new();
+    let convert = gst::ElementFactory::make("videoconvert").build()?;
+    let scale = gst::ElementFactory::make("videoscale").build()?;
+    let video_sink = gst_app::AppSink::builder()
+        .name("iced_video")
+        .caps(
+            &gst::Caps::builder("video/x-raw")
+                .field("format", "NV12")
+                .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+                .build(),
+        )
+        .drop(true)
+        .build();
+
+    let elements = [&source, &convert, &scale, video_sink.upcast_ref()];
+    pipeline.add_many(elements)?;
+    gst::Element::link_many(elements)?;
+
+    Ok((pipeline, video_sink))
+}
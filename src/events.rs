@@ -0,0 +1,75 @@
+use crate::PipelineError;
+use gstreamer as gst;
+use iced::futures::channel::mpsc;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// A message posted by the pipeline, see [`Video::events`](crate::Video::events).
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// The media finished playing, including any loops, and playback paused.
+    EndOfStream,
+    /// An element of the pipeline failed.
+    Error(PipelineError),
+    /// The source is buffering, with how full its buffer is in percent;
+    /// playback continues once it reaches 100.
+    Buffering(i32),
+    /// The media announced tags (e.g., its title or codecs).
+    Tags(gst::TagList),
+}
+
+impl Event {
+    fn from_message(msg: &gst::Message) -> Option<Self> {
+        match msg.view() {
+            gst::MessageView::Error(err) => Some(Event::Error(PipelineError::from_message(err))),
+            gst::MessageView::Buffering(buffering) => Some(Event::Buffering(buffering.percent())),
+            gst::MessageView::Tag(tag) => Some(Event::Tags(tag.tags())),
+            _ => None,
+        }
+    }
+}
+
+/// The channels of every running [`Video::events`](crate::Video::events) subscription.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Subscribers(Arc<Mutex<Vec<mpsc::UnboundedSender<Event>>>>);
+
+impl Subscribers {
+    /// Forwards the events posted to `bus` as they are posted, from whichever
+    /// thread posts them, so that they arrive even while nothing is drawn.
+    ///
+    /// The messages stay on the bus for the widget.
+    pub(crate) fn forward(&self, bus: &gst::Bus) {
+        let subscribers = self.clone();
+        bus.set_sync_handler(move |_, msg| {
+            if let Some(event) = Event::from_message(msg) {
+                subscribers.send(event);
+            }
+            gst::BusSyncReply::Pass
+        });
+    }
+
+    /// Sends `event` to every running subscription.
+    pub(crate) fn send(&self, event: Event) {
+        if let Ok(mut subscribers) = self.0.lock() {
+            // the receivers of finished subscriptions are dropped
+            subscribers.retain(|sender| sender.unbounded_send(event.clone()).is_ok());
+        }
+    }
+
+    pub(crate) fn subscription(&self) -> iced::Subscription<Event> {
+        iced::Subscription::run_with(self.clone(), |subscribers| {
+            let (sender, receiver) = mpsc::unbounded();
+            if let Ok(mut subscribers) = subscribers.0.lock() {
+                subscribers.push(sender);
+            }
+            receiver
+        })
+    }
+}
+
+impl Hash for Subscribers {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // identifies the video, so each video has its own subscription
+        Arc::as_ptr(&self.0).hash(state);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Error, PipelineError};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use gstreamer_video::VideoInfo;
+use std::ops::Range;
+use std::time::Duration;
+
+/// A frame decoded by [`Video::frames`](crate::Video::frames).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedFrame {
+    /// The position of the frame in the media.
+    pub pts: Duration,
+    /// The width of the frame in pixels.
+    pub width: u32,
+    /// The height of the frame in pixels.
+    pub height: u32,
+    /// The packed 8-bit RGBA data, `width * height * 4` bytes.
+    pub data: Vec<u8>,
+}
+
+/// Decodes frames of a media on a pipeline separate from playback, see
+/// [`Video::frames`](crate::Video::frames).
+///
+/// Decoding stops at the end of the range, or on the first error, which is
+/// kept in [`Frames::error`].
+#[derive(Debug)]
+pub struct Frames {
+    pipeline: gst::Pipeline,
+    sink: gst_app::AppSink,
+    step: Duration,
+    next: Duration,
+    error: Option<PipelineError>,
+}
+
+impl Frames {
+    pub(crate) fn new(uri: &str, range: Range<Duration>, step: Duration) -> Result<Self, Error> {
+        let pipeline = gst::parse::launch(&format!(
+            "uridecodebin uri=\"{uri}\" ! videoconvert ! videoscale ! appsink name=iced_frames sync=false caps=video/x-raw,format=RGBA,pixel-aspect-ratio=1/1"
+        ))?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| Error::Cast("gst::Pipeline"))?;
+        let sink = pipeline
+            .by_name("iced_frames")
+            .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+            .ok_or_else(|| Error::AppSink("iced_frames".into()))?;
+
+        let frames = Frames {
+            pipeline,
+            sink,
+            step,
+            next: range.start,
+            error: None,
+        };
+        frames.pipeline.set_state(gst::State::Paused)?;
+        frames.pipeline.state(gst::ClockTime::from_seconds(5)).0?;
+        frames.pipeline.seek(
+            1.0,
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(range.start.as_nanos() as _),
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(range.end.as_nanos() as _),
+        )?;
+        frames.pipeline.set_state(gst::State::Playing)?;
+
+        Ok(frames)
+    }
+
+    /// The error decoding stopped at, if any.
+    pub fn error(&self) -> Option<&PipelineError> {
+        self.error.as_ref()
+    }
+}
+
+impl Iterator for Frames {
+    type Item = DecodedFrame;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Ok(sample) = self.sink.pull_sample() else {
+                // the end of the range was reached, or an element failed
+                self.error = self
+                    .pipeline
+                    .bus()
+                    .and_then(|bus| PipelineError::from_bus(&bus));
+                return None;
+            };
+            let buffer = sample.buffer()?;
+            let pts = Duration::from_nanos(buffer.pts()?.nseconds());
+            // frames in between steps are decoded, but skipped
+            if pts < self.next {
+                continue;
+            }
+            self.next = pts + self.step;
+
+            let info = VideoInfo::from_caps(sample.caps()?).ok()?;
+            let map = buffer.map_readable().ok()?;
+            return Some(DecodedFrame {
+                pts,
+                width: info.width(),
+                height: info.height(),
+                data: map.as_slice().to_vec(),
+            });
+        }
+    }
+}
+
+impl Drop for Frames {
+    fn drop(&mut self) {
+        let _ = self.pipeline.set_state(gst::State::Null);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::Error;
+use crate::video::{Internal, PlaybackStatus, Position};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::sync::{RwLock, Weak};
+use std::time::Duration;
+
+/// Controls a [`Video`](crate::Video) from other threads (e.g., a remote
+/// control server), see [`Video::handle`](crate::Video::handle).
+///
+/// Handles are cheap to clone and don't keep the video alive. Once the video
+/// is dropped, commands fail with [`Error::Closed`] and queries return `None`.
+#[derive(Debug, Clone)]
+pub struct VideoHandle(pub(crate) Weak<RwLock<Internal>>);
+
+impl VideoHandle {
+    fn with<T>(&self, f: impl FnOnce(&mut Internal) -> T) -> Result<T, Error> {
+        let video = self.0.upgrade().ok_or(Error::Closed)?;
+        let mut inner = video.write().map_err(|_| Error::Lock)?;
+        Ok(f(&mut inner))
+    }
+
+    /// Get if the video still exists.
+    pub fn is_alive(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+
+    /// Set if the media is paused or not, see [`Video::set_paused`](crate::Video::set_paused).
+    pub fn set_paused(&self, paused: bool) -> Result<(), Error> {
+        self.with(|inner| inner.set_paused(paused))?
+    }
+
+    /// Get if the media is paused or not.
+    pub fn paused(&self) -> Option<bool> {
+        self.with(|inner| inner.paused()).ok()
+    }
+
+    /// Get the playback status, which also accounts for buffering and stalls.
+    pub fn status(&self) -> Option<PlaybackStatus> {
+        self.with(|inner| inner.status()).ok()
+    }
+
+    /// Jumps to a specific position in the media, see [`Video::seek`](crate::Video::seek).
+    pub fn seek(&self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
+        self.with(|inner| inner.seek(position, accurate))?
+    }
+
+    /// Set the playback speed of the media, see [`Video::set_speed`](crate::Video::set_speed).
+    pub fn set_speed(&self, speed: f64) -> Result<(), Error> {
+        self.with(|inner| inner.set_speed(speed))?
+    }
+
+    /// Set the volume multiplier of the audio, cancelling any fade in progress.
+    pub fn set_volume(&self, volume: f64) -> Result<(), Error> {
+        self.with(|inner| inner.set_volume(volume))
+    }
+
+    /// Set if the audio is muted or not.
+    pub fn set_muted(&self, muted: bool) -> Result<(), Error> {
+        self.with(|inner| inner.source.set_property("mute", muted))
+    }
+
+    /// Get the current playback position in time.
+    pub fn position(&self) -> Option<Duration> {
+        self.with(|inner| inner.source.query_position::<gst::ClockTime>())
+            .ok()
+            .flatten()
+            .map(|position| Duration::from_nanos(position.nseconds()))
+    }
+
+    /// Get the media duration.
+    pub fn duration(&self) -> Option<Duration> {
+        self.with(|inner| inner.duration).ok()
+    }
+}
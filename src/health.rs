@@ -0,0 +1,82 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::time::Duration;
+
+/// Network statistics of a packet-based (e.g., RTSP or RTP over UDP) source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct StreamHealth {
+    /// Packets received and passed on for decoding.
+    pub packets_received: u64,
+    /// Packets which never arrived.
+    pub packets_lost: u64,
+    /// Packets which arrived too late to be played.
+    pub packets_late: u64,
+    /// The average interarrival jitter of the received packets.
+    pub jitter: Duration,
+    /// The round-trip time reported by RTCP receiver reports, if any.
+    pub round_trip: Option<Duration>,
+}
+
+impl StreamHealth {
+    /// The fraction (`0.0..=1.0`) of packets which were lost.
+    pub fn loss_ratio(&self) -> f64 {
+        let total = self.packets_received + self.packets_lost;
+        if total == 0 {
+            0.0
+        } else {
+            self.packets_lost as f64 / total as f64
+        }
+    }
+}
+
+/// Gathers the statistics of the RTP jitter buffers and sessions in the pipeline.
+///
+/// Returns `None` if the source is not packet-based.
+pub(crate) fn collect(pipeline: &gst::Pipeline) -> Option<StreamHealth> {
+    let mut health = None::<StreamHealth>;
+    let mut jitter_buffers = 0;
+
+    for element in pipeline.iterate_recurse().into_iter().flatten() {
+        let Some(factory) = element.factory() else {
+            continue;
+        };
+        match factory.name().as_str() {
+            "rtpjitterbuffer" => {
+                let stats = element.property::<gst::Structure>("stats");
+                let health = health.get_or_insert_default();
+                let field = |name: &str| stats.get::<u64>(name).unwrap_or(0);
+                health.packets_received += field("num-pushed");
+                health.packets_lost += field("num-lost");
+                health.packets_late += field("num-late");
+                health.jitter += Duration::from_nanos(field("avg-jitter"));
+                jitter_buffers += 1;
+            }
+            "rtpsession" => {
+                let stats = element.property::<gst::Structure>("stats");
+                let Ok(sources) = stats.get::<glib::ValueArray>("source-stats") else {
+                    continue;
+                };
+                let round_trip = sources
+                    .iter()
+                    .filter_map(|source| source.get::<gst::Structure>().ok())
+                    .filter(|source| source.get::<bool>("have-rb").unwrap_or(false))
+                    .filter_map(|source| source.get::<u32>("rb-round-trip").ok())
+                    // expressed in 1/65536 seconds
+                    .map(|round_trip| Duration::from_secs_f64(round_trip as f64 / 65536.0))
+                    .max();
+                if let Some(round_trip) = round_trip {
+                    let health = health.get_or_insert_default();
+                    health.round_trip = health.round_trip.max(Some(round_trip));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(health) = &mut health
+        && jitter_buffers > 1
+    {
+        health.jitter /= jitter_buffers;
+    }
+    health
+}
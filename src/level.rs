@@ -0,0 +1,193 @@
+use gstreamer as gst;
+use std::time::Duration;
+
+/// The audio levels of each channel over one interval, see
+/// [`VideoBuilder::audio_levels`](crate::VideoBuilder::audio_levels).
+///
+/// Levels are in decibels relative to full scale, i.e., `0.0` is the loudest
+/// possible level and silence tends towards negative infinity.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AudioLevel {
+    /// The position in the media at which the interval started.
+    pub position: Duration,
+    /// The peak level of each channel.
+    pub peak: Vec<f64>,
+    /// The root mean square (i.e., perceived loudness) level of each channel.
+    pub rms: Vec<f64>,
+}
+
+impl AudioLevel {
+    /// Converts a level in decibels to a linear amplitude in `0.0..=1.0`,
+    /// e.g., to size the bar of a VU meter.
+    pub fn to_linear(db: f64) -> f64 {
+        10f64.powf(db / 20.0).clamp(0.0, 1.0)
+    }
+
+    /// Parses the message posted by the `level` element.
+    pub(crate) fn from_structure(structure: &gst::StructureRef) -> Option<Self> {
+        if structure.name() != "level" {
+            return None;
+        }
+        let channels = |field: &str| -> Option<Vec<f64>> {
+            let values = structure.get::<glib::ValueArray>(field).ok()?;
+            Some(
+                values
+                    .iter()
+                    .filter_map(|value| value.get::<f64>().ok())
+                    .collect(),
+            )
+        };
+        Some(AudioLevel {
+            position: Duration::from_nanos(structure.get::<u64>("stream-time").unwrap_or(0)),
+            peak: channels("peak")?,
+            rms: channels("rms")?,
+        })
+    }
+}
+
+/// The start or end of silence, see [`SilenceDetector::update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Silence {
+    Start(Duration),
+    End(Duration),
+}
+
+/// Finds stretches of audio which stay below a level, see
+/// [`VideoBuilder::silence_detection`](crate::VideoBuilder::silence_detection).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SilenceDetector {
+    threshold: f64,
+    min_duration: Duration,
+    quiet_since: Option<Duration>,
+    silent: bool,
+}
+
+impl SilenceDetector {
+    pub(crate) fn new(threshold: f64, min_duration: Duration) -> Self {
+        SilenceDetector {
+            threshold,
+            min_duration,
+            quiet_since: None,
+            silent: false,
+        }
+    }
+
+    /// Feeds the next levels, returning whether silence started or ended.
+    ///
+    /// Silence starts once every channel stayed below the threshold for the
+    /// minimum duration, and is reported at the position it began.
+    pub(crate) fn update(&mut self, level: &AudioLevel) -> Option<Silence> {
+        let quiet = !level.rms.is_empty() && level.rms.iter().all(|&rms| rms < self.threshold);
+
+        // the position jumped back, e.g., after seeking or looping
+        if self.quiet_since.is_some_and(|since| level.position < since) {
+            self.quiet_since = None;
+            if std::mem::take(&mut self.silent) {
+                return Some(Silence::End(level.position));
+            }
+        }
+
+        if !quiet {
+            self.quiet_since = None;
+            return std::mem::take(&mut self.silent).then_some(Silence::End(level.position));
+        }
+
+        let since = *self.quiet_since.get_or_insert(level.position);
+        if !self.silent && level.position.saturating_sub(since) >= self.min_duration {
+            self.silent = true;
+            return Some(Silence::Start(since));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(millis: u64, rms: &[f64]) -> AudioLevel {
+        AudioLevel {
+            position: Duration::from_millis(millis),
+            peak: rms.to_vec(),
+            rms: rms.to_vec(),
+        }
+    }
+
+    fn detector() -> SilenceDetector {
+        SilenceDetector::new(-50.0, Duration::from_secs(1))
+    }
+
+    #[test]
+    fn reports_silence_where_it_began() {
+        let mut detector = detector();
+        assert_eq!(detector.update(&level(0, &[-20.0])), None);
+        assert_eq!(detector.update(&level(100, &[-60.0])), None);
+        assert_eq!(detector.update(&level(600, &[-60.0])), None);
+        assert_eq!(
+            detector.update(&level(1100, &[-60.0])),
+            Some(Silence::Start(Duration::from_millis(100)))
+        );
+        assert_eq!(detector.update(&level(1200, &[-60.0])), None);
+        assert_eq!(
+            detector.update(&level(1300, &[-20.0])),
+            Some(Silence::End(Duration::from_millis(1300)))
+        );
+        assert_eq!(detector.update(&level(1400, &[-20.0])), None);
+    }
+
+    #[test]
+    fn ignores_short_pauses() {
+        let mut detector = detector();
+        assert_eq!(detector.update(&level(0, &[-60.0])), None);
+        assert_eq!(detector.update(&level(900, &[-60.0])), None);
+        assert_eq!(detector.update(&level(1000, &[-20.0])), None);
+        // the quiet stretch starts over after the loud level
+        assert_eq!(detector.update(&level(1100, &[-60.0])), None);
+        assert_eq!(detector.update(&level(2000, &[-60.0])), None);
+        assert_eq!(
+            detector.update(&level(2100, &[-60.0])),
+            Some(Silence::Start(Duration::from_millis(1100)))
+        );
+    }
+
+    #[test]
+    fn requires_every_channel_below_threshold() {
+        let mut detector = detector();
+        assert_eq!(detector.update(&level(0, &[-60.0, -20.0])), None);
+        assert_eq!(detector.update(&level(1000, &[-60.0, -20.0])), None);
+        // a level at the threshold is not below it
+        assert_eq!(detector.update(&level(2000, &[-60.0, -50.0])), None);
+        assert_eq!(detector.update(&level(3000, &[-60.0, -50.0])), None);
+        // levels without channels are not silent
+        assert_eq!(detector.update(&level(4000, &[])), None);
+        assert_eq!(detector.update(&level(5000, &[])), None);
+    }
+
+    #[test]
+    fn ends_silence_when_seeking_back() {
+        let mut detector = detector();
+        assert_eq!(detector.update(&level(500, &[-60.0])), None);
+        assert_eq!(
+            detector.update(&level(1500, &[-60.0])),
+            Some(Silence::Start(Duration::from_millis(500)))
+        );
+        assert_eq!(
+            detector.update(&level(0, &[-60.0])),
+            Some(Silence::End(Duration::ZERO))
+        );
+        // the quiet stretch starts over after the seek
+        assert_eq!(detector.update(&level(1000, &[-60.0])), None);
+        assert_eq!(
+            detector.update(&level(2000, &[-60.0])),
+            Some(Silence::Start(Duration::from_millis(1000)))
+        );
+    }
+
+    #[test]
+    fn converts_decibels_to_linear() {
+        assert_eq!(AudioLevel::to_linear(0.0), 1.0);
+        assert_eq!(AudioLevel::to_linear(10.0), 1.0);
+        assert!((AudioLevel::to_linear(-20.0) - 0.1).abs() < 1e-9);
+        assert_eq!(AudioLevel::to_linear(f64::NEG_INFINITY), 0.0);
+    }
+}
@@ -33,16 +33,61 @@
 //! ```
 //!
 //! You can programmatically control the video (e.g., seek, pause, loop, grab thumbnails) by accessing various methods on [`Video`].
+//!
+//! ## Migrating from earlier versions
+//!
+//! Errors carry more detail, which changed some public types:
+//! - [`Error::Cast`] names the type the element failed to cast to, and [`Error::Caps`]
+//!   says what was missing from the capabilities. Match them as `Error::Cast(_)` and `Error::Caps(_)`.
+//! - [`VideoPlayer::on_error`] passes a [`PipelineError`] rather than a `glib::Error`.
+//!   The message of the former `glib::Error` is [`PipelineError::message`].
+//! - [`Error`] has new variants, so matches on it need a wildcard arm.
 
+mod adaptive;
+mod builder;
+mod cache;
+mod capture;
+mod events;
+mod frames;
+mod handle;
+mod health;
+mod language;
+mod level;
+mod lut;
 mod pipeline;
+mod recording;
+mod scene;
+mod state;
+mod stats;
+mod subtitle;
+mod thumbnail;
 mod video;
 mod video_player;
 
 use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_pbutils as gst_pbutils;
 use thiserror::Error;
 
-pub use video::Position;
-pub use video::{ AudioTag, TextTag, Video, VideoFilters};
+pub use adaptive::Quality;
+pub use builder::VideoBuilder;
+pub use cache::ThumbnailCache;
+pub use capture::CaptureDevice;
+pub use events::Event;
+pub use frames::{DecodedFrame, Frames};
+pub use handle::VideoHandle;
+pub use health::StreamHealth;
+pub use level::AudioLevel;
+pub use lut::CubeLut;
+pub use pipeline::{CustomShader, DynamicRange};
+pub use recording::{ExportPreset, RecordingSettings};
+pub use state::PlaybackState;
+pub use stats::PlaybackStats;
+pub use subtitle::{StyledSubtitle, SubtitleCue, SubtitleSpan};
+pub use thumbnail::{AdaptiveThumbnails, CancelHandle, SpriteSheet};
+pub use video::{AudioTag, Chapter, Effect, FrameInfo, RawFrame, TextTag, Video, VideoFilters};
+pub use video::{DownmixMode, SeekOptions, Snap};
+pub use video::{FrameFormat, LateFramePolicy, Orientation, PlaybackStatus, Position, ScrubMode};
 pub use video_player::*;
 
 #[derive(Debug, Error)]
@@ -57,20 +102,197 @@ pub enum Error {
     AppSink(String),
     #[error("{0}")]
     StateChange(#[from] gst::StateChangeError),
-    #[error("failed to cast gstreamer element")]
-    Cast,
+    #[error("failed to cast gstreamer element to {0}")]
+    Cast(&'static str),
     #[error("{0}")]
     Io(#[from] std::io::Error),
     #[error("invalid URI")]
     Uri,
-    #[error("failed to get media capabilities")]
-    Caps,
+    #[error("failed to get media capabilities: {0}")]
+    Caps(String),
     #[error("failed to query media duration or position")]
     Duration,
     #[error("failed to sync with playback")]
     Sync,
     #[error("failed to lock internal sync primitive")]
     Lock,
+    #[error("the video was dropped")]
+    Closed,
     #[error("invalid framerate: {0}")]
     Framerate(f64),
+    #[error("background thread terminated unexpectedly")]
+    Thread,
+    #[error("no subtitle cue at index {0}")]
+    Cue(usize),
+    #[error("invalid .cube LUT: {0}")]
+    Lut(String),
+    #[error("{0}")]
+    Pipeline(#[from] PipelineError),
+    #[error("missing plugins: {}", MissingPlugin::describe(.0))]
+    MissingPlugin(Vec<MissingPlugin>),
+}
+
+/// The category of a [`PipelineError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// A GStreamer plugin needed to play the media is not installed.
+    MissingPlugin,
+    /// The media could not be fetched over the network.
+    Network,
+    /// A local resource (e.g., a file or device) could not be accessed.
+    Resource,
+    /// The media could not be demuxed or decoded.
+    Decode,
+    /// The pipeline failed to change its state.
+    StateChange,
+    /// Any other error.
+    Other,
+}
+
+/// An error posted by an element of the GStreamer pipeline.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} ({category:?} error from {})", element.as_deref().unwrap_or("unknown element"))]
+pub struct PipelineError {
+    /// What kind of error occurred.
+    pub category: ErrorCategory,
+    /// The path of the element which posted the error, if known.
+    pub element: Option<String>,
+    /// The human-readable error message.
+    pub message: String,
+    /// Additional debug information, useful for bug reports.
+    pub debug: Option<String>,
+}
+
+impl PipelineError {
+    pub(crate) fn from_message(err: &gst::message::Error) -> Self {
+        let error = err.error();
+        let element = err.src().and_then(|src| src.downcast_ref::<gst::Element>());
+
+        let category = if let Some(err) = error.kind::<gst::CoreError>() {
+            match err {
+                gst::CoreError::MissingPlugin => ErrorCategory::MissingPlugin,
+                gst::CoreError::StateChange => ErrorCategory::StateChange,
+                _ => ErrorCategory::Other,
+            }
+        } else if let Some(err) = error.kind::<gst::StreamError>() {
+            match err {
+                gst::StreamError::CodecNotFound => ErrorCategory::MissingPlugin,
+                _ => ErrorCategory::Decode,
+            }
+        } else if error.kind::<gst::ResourceError>().is_some() {
+            let is_network = element
+                .and_then(|element| element.factory())
+                .and_then(|factory| {
+                    factory
+                        .metadata(gst::ELEMENT_METADATA_KLASS)
+                        .map(|klass| klass.contains("Network"))
+                })
+                .unwrap_or(false);
+            if is_network {
+                ErrorCategory::Network
+            } else {
+                ErrorCategory::Resource
+            }
+        } else {
+            ErrorCategory::Other
+        };
+
+        PipelineError {
+            category,
+            element: err.src().map(|src| src.path_string().to_string()),
+            message: error.message().to_owned(),
+            debug: err.debug().map(|debug| debug.to_string()),
+        }
+    }
+
+    /// Takes the first error posted to the bus, if any.
+    pub(crate) fn from_bus(bus: &gst::Bus) -> Option<Self> {
+        let message = bus.pop_filtered(&[gst::MessageType::Error])?;
+        match message.view() {
+            gst::MessageView::Error(err) => Some(Self::from_message(err)),
+            _ => None,
+        }
+    }
+}
+
+/// A GStreamer plugin needed to play the media which is not installed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingPlugin {
+    /// A human-readable description of the missing plugin (e.g., "H.265 decoder").
+    pub description: String,
+    /// The detail string passed to the system's plugin installer, if any.
+    pub installer_detail: Option<String>,
+}
+
+impl MissingPlugin {
+    pub(crate) fn from_message(msg: &gst::Message) -> Option<Self> {
+        if !gst_pbutils::functions::is_missing_plugin_message(msg) {
+            return None;
+        }
+        gst_pbutils::pb_utils_init();
+        Some(MissingPlugin {
+            description: gst_pbutils::functions::missing_plugin_message_get_description(msg)
+                .to_string(),
+            installer_detail: gst_pbutils::functions::missing_plugin_message_get_installer_detail(
+                msg,
+            )
+            .map(|detail| detail.to_string()),
+        })
+    }
+
+    fn describe(plugins: &[Self]) -> String {
+        let descriptions: Vec<_> = plugins
+            .iter()
+            .map(|plugin| plugin.description.as_str())
+            .collect();
+        descriptions.join(", ")
+    }
+
+    /// Takes every missing plugin message posted to the bus, dropping the other
+    /// element messages. Only for pipelines which failed to start, as the
+    /// widget reads element messages of running ones.
+    pub(crate) fn from_bus(bus: &gst::Bus) -> Vec<Self> {
+        std::iter::from_fn(|| bus.pop_filtered(&[gst::MessageType::Element]))
+            .filter_map(|msg| Self::from_message(&msg))
+            .collect()
+    }
+
+    /// Returns whether a plugin installer is available on this system
+    /// (e.g., PackageKit on Linux).
+    pub fn installer_available() -> bool {
+        gst_pbutils::functions::install_plugins_supported()
+    }
+
+    /// Asks the system's plugin installer to install the missing plugin, without blocking.
+    ///
+    /// `on_done` is called from another thread once the installer exits, with whether
+    /// the plugin was installed. The video must be recreated to make use of it.
+    /// Returns `false` if there is no installer or nothing to pass to it.
+    pub fn install<F>(&self, on_done: F) -> bool
+    where
+        F: FnOnce(bool) + Send + 'static,
+    {
+        let Some(detail) = self.installer_detail.clone() else {
+            return false;
+        };
+        if !Self::installer_available() {
+            return false;
+        }
+        // the async variant reports back through the default GLib main loop,
+        // which iced apps don't run, so the installer is waited on instead
+        std::thread::spawn(move || {
+            let ret = gst_pbutils::functions::install_plugins_sync(&[&detail], None);
+            let installed = matches!(
+                ret,
+                gst_pbutils::InstallPluginsReturn::Success
+                    | gst_pbutils::InstallPluginsReturn::PartialSuccess
+            );
+            if installed {
+                // make the newly installed plugins visible to this process
+                let _ = gst::Registry::update();
+            }
+            on_done(installed);
+        });
+        true
+    }
 }
@@ -0,0 +1,212 @@
+use crate::Error;
+use std::path::Path;
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+/// The largest `LUT_3D_SIZE` accepted, as allowed by the `.cube` specification.
+const MAX_SIZE: u32 = 256;
+
+/// A 3D color lookup table, as used for color grading, see [`Video::set_lut`](crate::Video::set_lut).
+///
+/// It is applied on the GPU, with trilinear interpolation between its entries.
+#[derive(Debug, Clone)]
+pub struct CubeLut {
+    pub(crate) id: u64,
+    title: Option<String>,
+    size: u32,
+    pub(crate) domain_min: [f32; 3],
+    pub(crate) domain_max: [f32; 3],
+    /// The entries packed as `Rgb10a2Unorm`, red changing fastest.
+    pub(crate) data: Arc<[u32]>,
+}
+
+impl CubeLut {
+    /// Parses a LUT in the Adobe/Resolve `.cube` format. Only 3D LUTs are supported.
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut title = None;
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut data = Vec::new();
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || Error::Lut(format!("invalid line {}: {line}", index + 1));
+            let mut words = line.split_whitespace();
+            let Some(keyword) = words.next() else {
+                continue;
+            };
+
+            match keyword {
+                "TITLE" => {
+                    title = Some(line["TITLE".len()..].trim().trim_matches('"').to_owned());
+                }
+                "LUT_3D_SIZE" => {
+                    let value = words
+                        .next()
+                        .and_then(|value| value.parse::<u32>().ok())
+                        .filter(|size| (2..=MAX_SIZE).contains(size))
+                        .ok_or_else(invalid)?;
+                    size = Some(value);
+                }
+                "LUT_1D_SIZE" => return Err(Error::Lut("1D LUTs are not supported".into())),
+                "DOMAIN_MIN" => domain_min = parse_triple(words).ok_or_else(invalid)?,
+                "DOMAIN_MAX" => domain_max = parse_triple(words).ok_or_else(invalid)?,
+                // other keywords (e.g., `LUT_3D_INPUT_RANGE`) are ignored
+                keyword if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {}
+                _ => {
+                    let [r, g, b] = parse_triple(line.split_whitespace()).ok_or_else(invalid)?;
+                    data.push(pack(r, g, b));
+                }
+            }
+        }
+
+        let size = size.ok_or_else(|| Error::Lut("missing LUT_3D_SIZE".into()))?;
+        let expected = (size * size * size) as usize;
+        if data.len() != expected {
+            return Err(Error::Lut(format!(
+                "expected {expected} entries, found {}",
+                data.len()
+            )));
+        }
+        if (0..3).any(|i| domain_max[i] <= domain_min[i]) {
+            return Err(Error::Lut("DOMAIN_MAX must exceed DOMAIN_MIN".into()));
+        }
+
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Ok(CubeLut {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            title,
+            size,
+            domain_min,
+            domain_max,
+            data: data.into(),
+        })
+    }
+
+    /// Reads and parses a `.cube` file, see [`CubeLut::parse`].
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Get the title of the LUT, if it has one.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the number of entries along each axis of the LUT.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+}
+
+fn parse_triple<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<[f32; 3]> {
+    let mut triple = [0.0; 3];
+    for value in &mut triple {
+        *value = words.next()?.parse().ok()?;
+    }
+    words.next().is_none().then_some(triple)
+}
+
+/// Packs a color into `Rgb10a2Unorm`, which is filterable unlike 32-bit floats
+/// and more precise than 8-bit channels.
+fn pack(r: f32, g: f32, b: f32) -> u32 {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 1023.0).round() as u32;
+    channel(r) | (channel(g) << 10) | (channel(b) << 20) | (3 << 30)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The entries of an identity LUT of `size`, red changing fastest.
+    fn identity(size: u32) -> String {
+        let max = (size - 1) as f32;
+        let mut entries = String::new();
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    entries.push_str(&format!(
+                        "{} {} {}\n",
+                        r as f32 / max,
+                        g as f32 / max,
+                        b as f32 / max
+                    ));
+                }
+            }
+        }
+        entries
+    }
+
+    #[test]
+    fn parses_identity() {
+        let source = format!(
+            "# comment\nTITLE \"Identity\"\nLUT_3D_SIZE 2\nLUT_3D_INPUT_RANGE 0 1\n\n{}",
+            identity(2)
+        );
+        let lut = CubeLut::parse(&source).unwrap();
+        assert_eq!(lut.title(), Some("Identity"));
+        assert_eq!(lut.size(), 2);
+        assert_eq!(lut.data.len(), 8);
+        assert_eq!(lut.data[0], 3 << 30);
+        assert_eq!(lut.data[1], 1023 | (3 << 30));
+        assert_eq!(lut.data[7], 1023 | (1023 << 10) | (1023 << 20) | (3 << 30));
+    }
+
+    #[test]
+    fn parses_domain() {
+        let source = format!(
+            "LUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n{}",
+            identity(2)
+        );
+        let lut = CubeLut::parse(&source).unwrap();
+        assert_eq!(lut.domain_max, [2.0; 3]);
+
+        let source = format!(
+            "LUT_3D_SIZE 2\nDOMAIN_MIN 1 0 0\nDOMAIN_MAX 1 1 1\n{}",
+            identity(2)
+        );
+        assert!(matches!(CubeLut::parse(&source), Err(Error::Lut(_))));
+    }
+
+    #[test]
+    fn rejects_out_of_range_sizes() {
+        for size in ["0", "1", "257", "-2", "two", ""] {
+            let source = format!("LUT_3D_SIZE {size}\n{}", identity(2));
+            assert!(
+                matches!(CubeLut::parse(&source), Err(Error::Lut(_))),
+                "size {size:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_tables() {
+        let sources = [
+            // no size
+            identity(2),
+            // too few entries
+            format!("LUT_3D_SIZE 3\n{}", identity(2)),
+            // too many entries
+            format!("LUT_3D_SIZE 2\n{}0 0 0\n", identity(2)),
+            // entries with the wrong number of channels
+            format!("LUT_3D_SIZE 2\n{}", identity(2).replace("\n", " 0\n")),
+            format!("LUT_3D_SIZE 2\n0 0\n{}", identity(2)),
+            // entries which aren't numbers
+            format!("LUT_3D_SIZE 2\n{}", identity(2).replacen('0', "0..5", 1)),
+            // 1D LUTs
+            String::from("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n"),
+        ];
+        for source in sources {
+            assert!(
+                matches!(CubeLut::parse(&source), Err(Error::Lut(_))),
+                "{source:?}"
+            );
+        }
+    }
+}
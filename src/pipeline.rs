@@ -1,4 +1,5 @@
-use crate::video::Frame;
+use crate::CubeLut;
+use crate::video::{Colorimetry, Effect, Frame, FrameFormat, Orientation};
 use iced_wgpu::primitive::{Pipeline, Primitive};
 use iced_wgpu::wgpu;
 use std::{
@@ -6,42 +7,228 @@ use std::{
     num::NonZero,
     sync::{
         Arc, Mutex,
-        atomic::{AtomicBool, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU8, AtomicU64, AtomicUsize, Ordering},
     },
 };
 
+/// The dynamic range of the surface video frames are rendered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynamicRange {
+    /// A standard dynamic range surface; the frame is clamped to the SDR range.
+    Sdr,
+    /// An extended range (e.g., scRGB) surface; super-whites are passed through.
+    Hdr,
+}
+
+impl DynamicRange {
+    fn of(format: wgpu::TextureFormat) -> Self {
+        match format {
+            wgpu::TextureFormat::Rgba16Float | wgpu::TextureFormat::Rgba32Float => {
+                DynamicRange::Hdr
+            }
+            _ => DynamicRange::Sdr,
+        }
+    }
+
+    pub(crate) fn load(range: &AtomicU8) -> Option<Self> {
+        match range.load(Ordering::SeqCst) {
+            1 => Some(DynamicRange::Sdr),
+            2 => Some(DynamicRange::Hdr),
+            _ => None,
+        }
+    }
+
+    fn store(self, range: &AtomicU8) {
+        range.store(
+            match self {
+                DynamicRange::Sdr => 1,
+                DynamicRange::Hdr => 2,
+            },
+            Ordering::SeqCst,
+        );
+    }
+}
+
 #[repr(C)]
 struct Uniforms {
     rect: [f32; 4],
+    // rows of the YUV to RGB matrix, each padded to a vec4
+    yuv2rgb: [[f32; 4]; 3],
+    // Y offset, Y scale, UV scale
+    yuv_range: [f32; 4],
+    // solid color drawn instead of the frame, if alpha is non-zero
+    fill: [f32; 4],
+    // the part of the displayed frame to sample, as `[left, top, right, bottom]`
+    crop: [f32; 4],
+    // parameters of a `CustomShader`
+    custom: [[f32; 4]; 4],
+    // maps colors to LUT coordinates, `w` of the scale is 1 if a LUT is applied
+    lut_scale: [f32; 4],
+    lut_offset: [f32; 4],
+    hdr_output: u32,
+    // clockwise quarter turns, plus 4 if flipped horizontally
+    orientation: u32,
+    opacity: f32,
+    // how far the frame has faded in from black
+    fade: f32,
+    effect: u32,
+    effect_strength: f32,
+    // 1 if the frame is RGBA in `texture_y` rather than NV12
+    packed_rgb: u32,
     // because wgpu min_uniform_buffer_offset_alignment
-    _pad: [u8; 240],
+    _pad: [u8; UNIFORMS_PADDING],
 }
 
+const UNIFORMS_PADDING: usize = 20;
+const _: () = assert!(std::mem::size_of::<Uniforms>() == 256);
+
+/// How a frame is transformed and blended when drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FrameStyle {
+    pub orientation: Orientation,
+    /// The part of the displayed frame to show, as fractions of it.
+    pub crop: iced::Rectangle,
+    pub opacity: f32,
+    /// How far the frame has faded in from black, from `0.0` to `1.0`.
+    pub fade: f32,
+    pub effect: Effect,
+    /// The parameters of the [`CustomShader`], if any.
+    pub params: [[f32; 4]; 4],
+    /// Whether the LUT of the video, if any, is applied.
+    pub lut: bool,
+}
+
+impl Default for FrameStyle {
+    fn default() -> Self {
+        FrameStyle {
+            orientation: Orientation::default(),
+            crop: iced::Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 1.0,
+                height: 1.0,
+            },
+            opacity: 1.0,
+            fade: 1.0,
+            effect: Effect::None,
+            params: [[0.0; 4]; 4],
+            lut: false,
+        }
+    }
+}
+
+/// Leaves the frame as is when no [`CustomShader`] is set.
+const DEFAULT_POST_PROCESS: &str = "
+fn post_process(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32> {
+    return color;
+}
+";
+
+/// A WGSL snippet run on every pixel of the frame, after the built-in
+/// [`Effect`](crate::Effect), see [`VideoPlayer::shader`](crate::VideoPlayer::shader).
+///
+/// The snippet must define
+/// `fn post_process(color: vec3<f32>, uv: vec2<f32>) -> vec3<f32>`, which
+/// returns the color of the pixel at `uv` (texture coordinates, `0.0..=1.0`).
+/// Within it, `sample_rgb(uv)` samples the frame at any coordinates, and
+/// `uniforms.custom` holds the four `vec4<f32>` set with [`CustomShader::params`].
+///
+/// The snippet is compiled once per renderer. Invalid WGSL is reported by
+/// wgpu as a validation error, which panics by default.
+#[derive(Debug, Clone)]
+pub struct CustomShader {
+    id: u64,
+    source: Arc<str>,
+    params: [[f32; 4]; 4],
+}
+
+impl CustomShader {
+    /// Creates a shader from a WGSL snippet defining `post_process`.
+    ///
+    /// Create it once (e.g., in the state of the application) and clone it
+    /// when building the view, as each new shader is compiled anew.
+    pub fn new(source: impl Into<Arc<str>>) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        CustomShader {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            source: source.into(),
+            params: [[0.0; 4]; 4],
+        }
+    }
+
+    /// Sets the values of `uniforms.custom`, available to the snippet.
+    pub fn params(self, params: [[f32; 4]; 4]) -> Self {
+        CustomShader { params, ..self }
+    }
+}
+
+/// How many sets of textures of dropped videos are kept for reuse, e.g., for a
+/// wall of camera feeds which come and go.
+const MAX_POOLED: usize = 8;
+/// How many frames pooled textures are kept for before they are freed.
+const POOLED_FRAMES: usize = 120;
+
 struct VideoEntry {
     texture_y: wgpu::Texture,
     texture_uv: wgpu::Texture,
     instances: wgpu::Buffer,
     bg0: wgpu::BindGroup,
     alive: Arc<AtomicBool>,
+    colorimetry: Colorimetry,
+    size: (u32, u32),
+    format: FrameFormat,
+    version: u64,
+    lut: Option<EntryLut>,
 
     prepare_index: AtomicUsize,
     render_index: AtomicUsize,
 }
 
+/// A [`CubeLut`] uploaded as a 3D texture.
+struct EntryLut {
+    id: u64,
+    size: u32,
+    domain: ([f32; 3], [f32; 3]),
+    _texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+}
+
+impl EntryLut {
+    /// Maps colors within the domain onto the centers of the outermost texels.
+    fn transform(&self) -> ([f32; 4], [f32; 4]) {
+        let (min, max) = self.domain;
+        let texels = self.size as f32;
+        let scale: [f32; 3] =
+            std::array::from_fn(|i| (texels - 1.0) / (texels * (max[i] - min[i])));
+        let offset: [f32; 3] = std::array::from_fn(|i| 0.5 / texels - min[i] * scale[i]);
+        (
+            [scale[0], scale[1], scale[2], 1.0],
+            [offset[0], offset[1], offset[2], 0.0],
+        )
+    }
+}
+
 pub(crate) struct VideoPipeline {
     pipeline: wgpu::RenderPipeline,
+    // render pipelines of each `CustomShader` in use
+    custom_pipelines: BTreeMap<u64, wgpu::RenderPipeline>,
+    layout: wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
     bg0_layout: wgpu::BindGroupLayout,
+    lut_layout: wgpu::BindGroupLayout,
+    // bound in place of a LUT for videos without one
+    no_lut: wgpu::BindGroup,
     sampler: wgpu::Sampler,
+    // of the surface rendered to last, which may change as the window moves
+    // between monitors
+    output_range: AtomicU8,
     videos: BTreeMap<u64, VideoEntry>,
+    // textures of dropped videos, kept for reuse, with how many frames they were pooled for
+    pool: Vec<(VideoEntry, usize)>,
 }
 
 impl Pipeline for VideoPipeline {
     fn new(device: &wgpu::Device, _queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
-        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("iced_video_player shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
-        });
-
         let bg0_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("iced_video_player bind group 0 layout"),
             entries: &[
@@ -73,7 +260,7 @@ impl Pipeline for VideoPipeline {
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 3,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: true,
@@ -84,41 +271,27 @@ impl Pipeline for VideoPipeline {
             ],
         });
 
+        let lut_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("iced_video_player LUT bind group layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D3,
+                    multisampled: false,
+                },
+                count: None,
+            }],
+        });
+
         let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("iced_video_player pipeline layout"),
-            bind_group_layouts: &[&bg0_layout],
+            bind_group_layouts: &[&bg0_layout, &lut_layout],
             immediate_size: 0,
         });
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("iced_video_player pipeline"),
-            layout: Some(&layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            multiview_mask: None,
-            cache: None,
-        });
+        let pipeline = create_pipeline(device, &layout, format, DEFAULT_POST_PROCESS);
 
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("iced_video_player sampler"),
@@ -135,11 +308,24 @@ impl Pipeline for VideoPipeline {
             border_color: None,
         });
 
+        let (_, no_lut) = create_lut(device, &lut_layout, 1);
+
         VideoPipeline {
             pipeline,
+            custom_pipelines: BTreeMap::new(),
+            layout,
+            format,
             bg0_layout,
+            lut_layout,
+            no_lut,
             sampler,
+            output_range: {
+                let output_range = AtomicU8::new(0);
+                DynamicRange::of(format).store(&output_range);
+                output_range
+            },
             videos: BTreeMap::new(),
+            pool: Vec::new(),
         }
     }
 
@@ -151,133 +337,367 @@ impl Pipeline for VideoPipeline {
             .collect();
         for id in ids {
             if let Some(video) = self.videos.remove(&id) {
-                video.texture_y.destroy();
-                video.texture_uv.destroy();
-                video.instances.destroy();
+                self.release(video);
+            }
+        }
+
+        // nothing is left to reuse the pool once every video is gone
+        let max_frames = if self.videos.is_empty() {
+            0
+        } else {
+            POOLED_FRAMES
+        };
+        for (video, frames) in std::mem::take(&mut self.pool) {
+            if frames < max_frames {
+                self.pool.push((video, frames + 1));
+            } else {
+                destroy(video);
             }
         }
     }
 }
 
+/// Frees the textures of a video right away rather than once wgpu drops them.
+fn destroy(video: VideoEntry) {
+    video.texture_y.destroy();
+    video.texture_uv.destroy();
+    video.instances.destroy();
+}
+
+/// Creates a LUT texture of `size` entries along each axis, and its bind group.
+fn create_lut(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    size: u32,
+) -> (wgpu::Texture, wgpu::BindGroup) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("iced_video_player LUT texture"),
+        size: wgpu::Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: size,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D3,
+        format: wgpu::TextureFormat::Rgb10a2Unorm,
+        usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("iced_video_player LUT bind group"),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(&view),
+        }],
+    });
+
+    (texture, bind_group)
+}
+
+/// Creates the render pipeline of the shader, with `post_process` spliced in.
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+    post_process: &str,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("iced_video_player shader"),
+        source: wgpu::ShaderSource::Wgsl(
+            format!("{}\n{post_process}", include_str!("shader.wgsl")).into(),
+        ),
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("iced_video_player pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
 impl VideoPipeline {
-    fn upload(
+    /// Compiles the `shader`, unless it already was.
+    fn prepare_shader(&mut self, device: &wgpu::Device, shader: &CustomShader) {
+        if let Entry::Vacant(entry) = self.custom_pipelines.entry(shader.id) {
+            entry.insert(create_pipeline(
+                device,
+                &self.layout,
+                self.format,
+                &shader.source,
+            ));
+        }
+    }
+
+    /// Uploads the `lut` of a video, unless it already was.
+    fn prepare_lut(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         video_id: u64,
-        alive: &Arc<AtomicBool>,
-        (width, height): (u32, u32),
-        frame: &[u8],
-        stride: Option<u32>,
+        lut: Option<&CubeLut>,
     ) {
-        // Use stride from GStreamer's VideoMeta if available, otherwise assume stride == width
-        let stride = stride.unwrap_or(width);
-        if let Entry::Vacant(entry) = self.videos.entry(video_id) {
-            let texture_y = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("iced_video_player texture"),
-                size: wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
+        let Some(video) = self.videos.get_mut(&video_id) else {
+            return;
+        };
+        if video.lut.as_ref().map(|lut| lut.id) == lut.map(|lut| lut.id) {
+            return;
+        }
+        video.lut = lut.map(|lut| {
+            let (texture, bind_group) = create_lut(device, &self.lut_layout, lut.size());
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
                 },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::R8Unorm,
-                usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
-
-            let texture_uv = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("iced_video_player texture"),
-                size: wgpu::Extent3d {
-                    width: width / 2,
-                    height: height / 2,
-                    depth_or_array_layers: 1,
+                unsafe {
+                    std::slice::from_raw_parts(
+                        lut.data.as_ptr() as *const u8,
+                        std::mem::size_of_val(&*lut.data),
+                    )
                 },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rg8Unorm,
-                usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            });
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(lut.size() * 4),
+                    rows_per_image: Some(lut.size()),
+                },
+                wgpu::Extent3d {
+                    width: lut.size(),
+                    height: lut.size(),
+                    depth_or_array_layers: lut.size(),
+                },
+            );
+            EntryLut {
+                id: lut.id,
+                size: lut.size(),
+                domain: (lut.domain_min, lut.domain_max),
+                _texture: texture,
+                bind_group,
+            }
+        });
+    }
 
-            let view_y = texture_y.create_view(&wgpu::TextureViewDescriptor {
-                label: Some("iced_video_player texture view"),
-                format: None,
-                dimension: None,
-                aspect: wgpu::TextureAspect::All,
-                base_mip_level: 0,
-                mip_level_count: None,
-                base_array_layer: 0,
-                array_layer_count: None,
-                usage: None,
-            });
+    /// Get the dynamic range of the surface rendered to last.
+    fn output_range(&self) -> DynamicRange {
+        DynamicRange::load(&self.output_range).unwrap_or(DynamicRange::Sdr)
+    }
 
-            let view_uv = texture_uv.create_view(&wgpu::TextureViewDescriptor {
-                label: Some("iced_video_player texture view"),
-                format: None,
-                dimension: None,
-                aspect: wgpu::TextureAspect::All,
-                base_mip_level: 0,
-                mip_level_count: None,
-                base_array_layer: 0,
-                array_layer_count: None,
-                usage: None,
-            });
+    /// Keeps the textures of a video for reuse, or frees them if the pool is full.
+    fn release(&mut self, video: VideoEntry) {
+        if self.pool.len() < MAX_POOLED {
+            self.pool.push((video, 0));
+        } else {
+            destroy(video);
+        }
+    }
 
-            let instances = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("iced_video_player uniform buffer"),
-                size: 256 * std::mem::size_of::<Uniforms>() as u64, // max 256 video players per frame
-                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
-                mapped_at_creation: false,
-            });
+    fn create_entry(
+        &self,
+        device: &wgpu::Device,
+        alive: &Arc<AtomicBool>,
+        (width, height): (u32, u32),
+        format: FrameFormat,
+        colorimetry: Colorimetry,
+    ) -> VideoEntry {
+        let texture_y = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("iced_video_player texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: match format {
+                FrameFormat::Rgba => wgpu::TextureFormat::Rgba8Unorm,
+                FrameFormat::Nv12 => wgpu::TextureFormat::R8Unorm,
+            },
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
 
-            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("iced_video_player bind group"),
-                layout: &self.bg0_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&view_y),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::TextureView(&view_uv),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: wgpu::BindingResource::Sampler(&self.sampler),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 3,
-                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                            buffer: &instances,
-                            offset: 0,
-                            size: Some(NonZero::new(std::mem::size_of::<Uniforms>() as _).unwrap()),
-                        }),
-                    },
-                ],
-            });
+        // RGBA frames leave the UV texture unused
+        let (uv_width, uv_height) = match format {
+            FrameFormat::Rgba => (1, 1),
+            FrameFormat::Nv12 => (width / 2, height / 2),
+        };
+        let texture_uv = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("iced_video_player texture"),
+            size: wgpu::Extent3d {
+                width: uv_width,
+                height: uv_height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rg8Unorm,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
 
-            entry.insert(VideoEntry {
-                texture_y,
-                texture_uv,
-                instances,
-                bg0: bind_group,
-                alive: Arc::clone(alive),
+        let view_y = texture_y.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("iced_video_player texture view"),
+            format: None,
+            dimension: None,
+            aspect: wgpu::TextureAspect::All,
+            base_mip_level: 0,
+            mip_level_count: None,
+            base_array_layer: 0,
+            array_layer_count: None,
+            usage: None,
+        });
 
-                prepare_index: AtomicUsize::new(0),
-                render_index: AtomicUsize::new(0),
-            });
+        let view_uv = texture_uv.create_view(&wgpu::TextureViewDescriptor {
+            label: Some("iced_video_player texture view"),
+            format: None,
+            dimension: None,
+            aspect: wgpu::TextureAspect::All,
+            base_mip_level: 0,
+            mip_level_count: None,
+            base_array_layer: 0,
+            array_layer_count: None,
+            usage: None,
+        });
+
+        let instances = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("iced_video_player uniform buffer"),
+            size: 256 * std::mem::size_of::<Uniforms>() as u64, // max 256 video players per frame
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("iced_video_player bind group"),
+            layout: &self.bg0_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view_y),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view_uv),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &instances,
+                        offset: 0,
+                        size: Some(NonZero::new(std::mem::size_of::<Uniforms>() as _).unwrap()),
+                    }),
+                },
+            ],
+        });
+
+        VideoEntry {
+            texture_y,
+            texture_uv,
+            instances,
+            bg0: bind_group,
+            alive: Arc::clone(alive),
+            colorimetry,
+            size: (width, height),
+            format,
+            version: 0,
+            lut: None,
+
+            prepare_index: AtomicUsize::new(0),
+            render_index: AtomicUsize::new(0),
+        }
+    }
+
+    fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        video_id: u64,
+        alive: &Arc<AtomicBool>,
+        (width, height): (u32, u32),
+        frame: &Frame,
+    ) {
+        let format = frame.format();
+        // Use stride from GStreamer's VideoMeta if available, otherwise assume tightly packed rows
+        let stride = frame.stride().unwrap_or(match format {
+            FrameFormat::Rgba => width * 4,
+            FrameFormat::Nv12 => width,
+        });
+        let colorimetry = frame.colorimetry();
+        let Some(frame) = frame.readable() else {
+            return;
+        };
+        // the source changed its resolution (or format) mid-stream
+        if self
+            .videos
+            .get(&video_id)
+            .is_some_and(|entry| entry.size != (width, height) || entry.format != format)
+            && let Some(video) = self.videos.remove(&video_id)
+        {
+            self.release(video);
+        }
+
+        if !self.videos.contains_key(&video_id) {
+            // textures of the same size left behind by a dropped video are reused
+            let entry =
+                match self.pool.iter().position(|(pooled, _)| {
+                    pooled.size == (width, height) && pooled.format == format
+                }) {
+                    Some(index) => VideoEntry {
+                        alive: Arc::clone(alive),
+                        colorimetry,
+                        version: 0,
+                        prepare_index: AtomicUsize::new(0),
+                        render_index: AtomicUsize::new(0),
+                        ..self.pool.swap_remove(index).0
+                    },
+                    None => self.create_entry(device, alive, (width, height), format, colorimetry),
+                };
+            self.videos.insert(video_id, entry);
         }
 
         let VideoEntry {
             texture_y,
             texture_uv,
+            colorimetry: entry_colorimetry,
             ..
-        } = self.videos.get(&video_id).unwrap();
+        } = self.videos.get_mut(&video_id).unwrap();
+
+        *entry_colorimetry = colorimetry;
 
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
@@ -299,6 +719,10 @@ impl VideoPipeline {
             },
         );
 
+        if format == FrameFormat::Rgba {
+            return;
+        }
+
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
                 texture: texture_uv,
@@ -320,8 +744,21 @@ impl VideoPipeline {
         );
     }
 
-    fn prepare(&mut self, queue: &wgpu::Queue, video_id: u64, bounds: &iced::Rectangle) {
+    fn prepare(
+        &mut self,
+        queue: &wgpu::Queue,
+        video_id: u64,
+        bounds: &iced::Rectangle,
+        fill: Option<iced::Color>,
+        style: &FrameStyle,
+    ) {
         if let Some(video) = self.videos.get_mut(&video_id) {
+            let [r, g, b] = video.colorimetry.matrix;
+            let (lut_scale, lut_offset) = video
+                .lut
+                .as_ref()
+                .filter(|_| style.lut)
+                .map_or(([0.0; 4], [0.0; 4]), EntryLut::transform);
             let uniforms = Uniforms {
                 rect: [
                     bounds.x,
@@ -329,7 +766,35 @@ impl VideoPipeline {
                     bounds.x + bounds.width,
                     bounds.y + bounds.height,
                 ],
-                _pad: [0; 240],
+                yuv2rgb: [
+                    [r[0], r[1], r[2], 0.0],
+                    [g[0], g[1], g[2], 0.0],
+                    [b[0], b[1], b[2], 0.0],
+                ],
+                yuv_range: [
+                    video.colorimetry.y_range.0,
+                    video.colorimetry.y_range.1,
+                    video.colorimetry.uv_scale,
+                    0.0,
+                ],
+                fill: fill.map_or([0.0; 4], |color| [color.r, color.g, color.b, 1.0]),
+                crop: [
+                    style.crop.x,
+                    style.crop.y,
+                    style.crop.x + style.crop.width,
+                    style.crop.y + style.crop.height,
+                ],
+                hdr_output: (self.output_range() == DynamicRange::Hdr) as u32,
+                orientation: style.orientation.shader_value(),
+                opacity: style.opacity.clamp(0.0, 1.0),
+                fade: style.fade.clamp(0.0, 1.0),
+                effect: style.effect.shader_value(),
+                effect_strength: style.effect.strength(),
+                packed_rgb: (video.format == FrameFormat::Rgba) as u32,
+                custom: style.params,
+                lut_scale,
+                lut_offset,
+                _pad: [0; UNIFORMS_PADDING],
             };
             queue.write_buffer(
                 &video.instances,
@@ -353,6 +818,7 @@ impl VideoPipeline {
         encoder: &mut wgpu::CommandEncoder,
         clip: &iced_wgpu::core::Rectangle<u32>,
         video_id: u64,
+        shader: Option<&CustomShader>,
     ) {
         if let Some(video) = self.videos.get(&video_id) {
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -372,7 +838,18 @@ impl VideoPipeline {
                 multiview_mask: None,
             });
 
-            pass.set_pipeline(&self.pipeline);
+            let pipeline = shader
+                .and_then(|shader| self.custom_pipelines.get(&shader.id))
+                .unwrap_or(&self.pipeline);
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(
+                1,
+                video
+                    .lut
+                    .as_ref()
+                    .map_or(&self.no_lut, |lut| &lut.bind_group),
+                &[],
+            );
             pass.set_bind_group(
                 0,
                 &video.bg0,
@@ -396,7 +873,21 @@ pub(crate) struct VideoPrimitive {
     alive: Arc<AtomicBool>,
     frame: Arc<Mutex<Frame>>,
     size: (u32, u32),
-    upload_frame: bool,
+    version: u64,
+    style: FrameStyle,
+    shader: Option<CustomShader>,
+    lut: Option<CubeLut>,
+    output_range: Arc<AtomicU8>,
+    inset: Option<Box<InsetPrimitive>>,
+}
+
+/// A secondary video drawn on top of a [`VideoPrimitive`].
+#[derive(Debug, Clone)]
+pub(crate) struct InsetPrimitive {
+    pub video: VideoPrimitive,
+    pub bounds: iced::Rectangle,
+    pub border_width: f32,
+    pub border_color: iced::Color,
 }
 
 impl VideoPrimitive {
@@ -405,14 +896,111 @@ impl VideoPrimitive {
         alive: Arc<AtomicBool>,
         frame: Arc<Mutex<Frame>>,
         size: (u32, u32),
-        upload_frame: bool,
+        version: u64,
+        orientation: Orientation,
+        output_range: Arc<AtomicU8>,
     ) -> Self {
         VideoPrimitive {
             video_id,
             alive,
             frame,
             size,
-            upload_frame,
+            version,
+            style: FrameStyle {
+                orientation,
+                ..FrameStyle::default()
+            },
+            shader: None,
+            lut: None,
+            output_range,
+            inset: None,
+        }
+    }
+
+    /// Shows only the `crop` of the frame, as fractions of the displayed frame.
+    pub fn with_crop(self, crop: iced::Rectangle) -> Self {
+        VideoPrimitive {
+            style: FrameStyle { crop, ..self.style },
+            ..self
+        }
+    }
+
+    /// Blends the frame with what is behind it, and darkens it until it has faded in.
+    pub fn with_opacity(self, opacity: f32, fade: f32) -> Self {
+        VideoPrimitive {
+            style: FrameStyle {
+                opacity,
+                fade,
+                ..self.style
+            },
+            ..self
+        }
+    }
+
+    /// Applies a post-processing `effect` to the frame.
+    pub fn with_effect(self, effect: Effect) -> Self {
+        VideoPrimitive {
+            style: FrameStyle {
+                effect,
+                ..self.style
+            },
+            ..self
+        }
+    }
+
+    /// Runs the `shader` on every pixel of the frame.
+    pub fn with_shader(self, shader: CustomShader) -> Self {
+        VideoPrimitive {
+            style: FrameStyle {
+                params: shader.params,
+                ..self.style
+            },
+            shader: Some(shader),
+            ..self
+        }
+    }
+
+    /// Grades the frame with a 3D `lut`.
+    pub fn with_lut(self, lut: Option<CubeLut>) -> Self {
+        VideoPrimitive {
+            style: FrameStyle {
+                lut: lut.is_some(),
+                ..self.style
+            },
+            lut,
+            ..self
+        }
+    }
+
+    /// Composites `inset` on top of this video within the same primitive.
+    pub fn with_inset(self, inset: InsetPrimitive) -> Self {
+        VideoPrimitive {
+            inset: Some(Box::new(inset)),
+            ..self
+        }
+    }
+
+    /// Uploads the frame, unless another widget showing the same video already did.
+    fn upload(&self, pipeline: &mut VideoPipeline, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let uploaded = pipeline
+            .videos
+            .get(&self.video_id)
+            .is_some_and(|entry| entry.version >= self.version);
+        if !uploaded {
+            let frame_guard = self.frame.lock().expect("lock frame mutex");
+            // the frame may already have a new resolution the widget doesn't know of yet
+            let size = frame_guard.size().unwrap_or(self.size);
+            pipeline.upload(
+                device,
+                queue,
+                self.video_id,
+                &self.alive,
+                size,
+                &frame_guard,
+            );
+            if let Some(entry) = pipeline.videos.get_mut(&self.video_id) {
+                entry.version = self.version;
+            }
         }
     }
 }
@@ -428,31 +1016,56 @@ impl Primitive for VideoPrimitive {
         bounds: &iced::Rectangle,
         viewport: &iced_wgpu::graphics::Viewport,
     ) {
-        if self.upload_frame {
-            let frame_guard = self.frame.lock().expect("lock frame mutex");
-            let stride = frame_guard.stride();
-            if let Some(readable) = frame_guard.readable() {
-                pipeline.upload(
-                    device,
-                    queue,
-                    self.video_id,
-                    &self.alive,
-                    self.size,
-                    readable.as_slice(),
-                    stride,
-                );
-            };
+        // let the widget know which kind of surface it ended up on
+        pipeline.output_range().store(&self.output_range);
+
+        self.upload(pipeline, device, queue);
+        pipeline.prepare_lut(device, queue, self.video_id, self.lut.as_ref());
+        if let Some(shader) = &self.shader {
+            pipeline.prepare_shader(device, shader);
         }
 
+        let projection = iced_core::Transformation::orthographic(
+            viewport.logical_size().width as _,
+            viewport.logical_size().height as _,
+        );
         pipeline.prepare(
             queue,
             self.video_id,
-            &(*bounds
-                * iced_core::Transformation::orthographic(
-                    viewport.logical_size().width as _,
-                    viewport.logical_size().height as _,
-                )),
+            &(*bounds * projection),
+            None,
+            &self.style,
         );
+
+        // the inset's border and frame are drawn in this order by `render`
+        if let Some(inset) = &self.inset {
+            inset.video.upload(pipeline, device, queue);
+            pipeline.prepare_lut(
+                device,
+                queue,
+                inset.video.video_id,
+                inset.video.lut.as_ref(),
+            );
+            if inset.border_width > 0.0 {
+                pipeline.prepare(
+                    queue,
+                    inset.video.video_id,
+                    &(inset.bounds.expand(inset.border_width) * projection),
+                    Some(inset.border_color),
+                    &FrameStyle {
+                        opacity: inset.video.style.opacity,
+                        ..FrameStyle::default()
+                    },
+                );
+            }
+            pipeline.prepare(
+                queue,
+                inset.video.video_id,
+                &(inset.bounds * projection),
+                None,
+                &inset.video.style,
+            );
+        }
     }
 
     fn draw(&self, _renderer: &Self::Pipeline, _render_pass: &mut wgpu::RenderPass<'_>) -> bool {
@@ -466,6 +1079,22 @@ impl Primitive for VideoPrimitive {
         target: &wgpu::TextureView,
         clip_bounds: &iced_wgpu::core::Rectangle<u32>,
     ) {
-        pipeline.draw(target, encoder, clip_bounds, self.video_id);
+        // picked up by the next `prepare`, as the target isn't known before
+        DynamicRange::of(target.texture().format()).store(&pipeline.output_range);
+
+        pipeline.draw(
+            target,
+            encoder,
+            clip_bounds,
+            self.video_id,
+            self.shader.as_ref(),
+        );
+
+        if let Some(inset) = &self.inset {
+            if inset.border_width > 0.0 {
+                pipeline.draw(target, encoder, clip_bounds, inset.video.video_id, None);
+            }
+            pipeline.draw(target, encoder, clip_bounds, inset.video.video_id, None);
+        }
     }
 }
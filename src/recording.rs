@@ -0,0 +1,195 @@
+use crate::{Error, PipelineError};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How [`Video::start_recording`](crate::Video::start_recording) encodes the recorded frames.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordingSettings {
+    /// The encoder element and its properties, in `gst-launch` syntax.
+    pub encoder: String,
+    /// The muxer element, which decides the container format (e.g., `mp4mux`
+    /// or `matroskamux`).
+    pub muxer: String,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        RecordingSettings {
+            encoder: String::from("x264enc tune=zerolatency speed-preset=veryfast"),
+            muxer: String::from("mp4mux"),
+        }
+    }
+}
+
+/// How [`Video::export_clip`](crate::Video::export_clip) transcodes the clip.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExportPreset {
+    /// The video encoder element and its properties, in `gst-launch` syntax.
+    pub video_encoder: String,
+    /// The audio encoder element and its properties, in `gst-launch` syntax.
+    pub audio_encoder: String,
+    /// The muxer element, which decides the container format (e.g., `mp4mux`
+    /// or `matroskamux`).
+    pub muxer: String,
+}
+
+impl Default for ExportPreset {
+    fn default() -> Self {
+        ExportPreset {
+            video_encoder: String::from("x264enc speed-preset=medium"),
+            audio_encoder: String::from("avenc_aac"),
+            muxer: String::from("mp4mux"),
+        }
+    }
+}
+
+/// Transcodes the range `start..end` of the media at `uri` to a file at `path`,
+/// on a pipeline separate from playback. Blocks until the file is written.
+pub(crate) fn export(
+    uri: &str,
+    start: Duration,
+    end: Duration,
+    path: &Path,
+    preset: &ExportPreset,
+    on_progress: impl Fn(f64),
+) -> Result<(), Error> {
+    let pipeline = gst::parse::launch(&format!(
+        "uridecodebin uri=\"{uri}\" name=dec \
+         dec. ! queue ! videoconvert ! {} ! mux. \
+         dec. ! queue ! audioconvert ! audioresample ! {} ! mux. \
+         {} name=mux ! filesink name=iced_file",
+        preset.video_encoder, preset.audio_encoder, preset.muxer
+    ))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| Error::Cast("gst::Pipeline"))?;
+    pipeline
+        .by_name("iced_file")
+        .ok_or(Error::Cast("gst::Element"))?
+        .set_property("location", path);
+    let bus = pipeline.bus().ok_or(Error::Bus)?;
+
+    // We need to ensure we stop the pipeline if we hit an error,
+    // or else a half-written file may be left open.
+    let result = (|| -> Result<(), Error> {
+        pipeline.set_state(gst::State::Paused)?;
+        pipeline.state(gst::ClockTime::from_seconds(5)).0?;
+        pipeline.seek(
+            1.0,
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(start.as_nanos() as _),
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(end.as_nanos() as _),
+        )?;
+        pipeline.set_state(gst::State::Playing)?;
+
+        let length = end.saturating_sub(start).as_secs_f64();
+        loop {
+            let message = bus.timed_pop_filtered(
+                gst::ClockTime::from_mseconds(100),
+                &[gst::MessageType::Eos, gst::MessageType::Error],
+            );
+            match message.as_ref().map(|message| message.view()) {
+                Some(gst::MessageView::Eos(_)) => break,
+                Some(gst::MessageView::Error(err)) => {
+                    return Err(PipelineError::from_message(err).into());
+                }
+                _ => {}
+            }
+            if let Some(position) = pipeline.query_position::<gst::ClockTime>()
+                && length > 0.0
+            {
+                let done = Duration::from_nanos(position.nseconds()).saturating_sub(start);
+                on_progress((done.as_secs_f64() / length).clamp(0.0, 1.0));
+            }
+        }
+        on_progress(1.0);
+        Ok(())
+    })();
+
+    pipeline.set_state(gst::State::Null)?;
+    result
+}
+
+/// Encodes the frames presented by a [`Video`](crate::Video) to a file.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    pipeline: gst::Pipeline,
+    src: gst_app::AppSrc,
+    start: Option<Instant>,
+    last: Option<gst::ClockTime>,
+}
+
+impl Recorder {
+    pub(crate) fn new(path: &Path, settings: &RecordingSettings) -> Result<Self, Error> {
+        let pipeline = gst::parse::launch(&format!(
+            "appsrc name=iced_recording format=time ! queue ! videoconvert ! {} ! {} ! filesink name=iced_file",
+            settings.encoder, settings.muxer
+        ))?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| Error::Cast("gst::Pipeline"))?;
+        let src = pipeline
+            .by_name("iced_recording")
+            .and_then(|src| src.downcast::<gst_app::AppSrc>().ok())
+            .ok_or(Error::Cast("gst_app::AppSrc"))?;
+        pipeline
+            .by_name("iced_file")
+            .ok_or(Error::Cast("gst::Element"))?
+            .set_property("location", path);
+
+        pipeline.set_state(gst::State::Playing)?;
+
+        Ok(Recorder {
+            pipeline,
+            src,
+            start: None,
+            last: None,
+        })
+    }
+
+    /// Queues a presented frame for encoding. The prerolled frame, which is
+    /// presented over and over while paused, is only recorded once.
+    pub(crate) fn push(&mut self, sample: &gst::Sample) {
+        let Some(mut buffer) = sample.buffer_owned() else {
+            return;
+        };
+        if buffer.pts().is_some() && buffer.pts() == self.last {
+            return;
+        }
+        self.last = buffer.pts();
+
+        if self.src.caps().as_ref() != sample.caps() {
+            self.src.set_caps(sample.caps());
+        }
+
+        // frames are timestamped as they are presented, so that seeks don't
+        // make the timeline of the file jump around
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let buffer_mut = buffer.make_mut();
+        buffer_mut.set_pts(gst::ClockTime::from_nseconds(
+            start.elapsed().as_nanos() as _
+        ));
+        buffer_mut.set_dts(gst::ClockTime::NONE);
+        buffer_mut.set_duration(gst::ClockTime::NONE);
+        let _ = self.src.push_buffer(buffer);
+    }
+
+    /// Ends the recording, waiting for the file to be finalized.
+    pub(crate) fn finish(self) -> Result<(), Error> {
+        let _ = self.src.end_of_stream();
+        let message = self.pipeline.bus().and_then(|bus| {
+            bus.timed_pop_filtered(
+                gst::ClockTime::from_seconds(10),
+                &[gst::MessageType::Eos, gst::MessageType::Error],
+            )
+        });
+        self.pipeline.set_state(gst::State::Null)?;
+        match message.as_ref().map(|message| message.view()) {
+            Some(gst::MessageView::Error(err)) => Err(PipelineError::from_message(err).into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use gstreamer as gst;
+use gstreamer_video::{VideoFormat, VideoInfo, VideoMeta};
+use std::time::Duration;
+
+/// How many luma levels are compared, each grouping 4 of the 256 levels.
+const BINS: usize = 64;
+
+/// Only every 4th pixel of every 4th row is sampled, which is plenty for a histogram.
+const STEP: usize = 4;
+
+/// Detects cuts by comparing the luma histograms of consecutive frames, see
+/// [`Video::set_scene_detection`](crate::Video::set_scene_detection).
+#[derive(Debug)]
+pub(crate) struct SceneDetector {
+    threshold: f64,
+    previous: Option<[u32; BINS]>,
+    /// The positions of the scene changes which were not yet sent.
+    pub(crate) changes: Vec<Duration>,
+}
+
+impl SceneDetector {
+    pub(crate) fn new(threshold: f64) -> Self {
+        SceneDetector {
+            threshold: threshold.clamp(0.0, 1.0),
+            previous: None,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Compares the NV12 or RGBA frame of `sample` to the previous one.
+    pub(crate) fn push(&mut self, sample: &gst::Sample) {
+        let Some(buffer) = sample.buffer() else {
+            return;
+        };
+        let Some(info) = sample
+            .caps()
+            .and_then(|caps| VideoInfo::from_caps(caps).ok())
+        else {
+            return;
+        };
+        let Ok(map) = buffer.map_readable() else {
+            return;
+        };
+        let stride = buffer
+            .meta::<VideoMeta>()
+            .map_or(info.stride()[0], |meta| meta.stride()[0])
+            .max(1) as usize;
+
+        let histogram = histogram(
+            &map,
+            info.width() as usize,
+            info.height() as usize,
+            stride,
+            info.format() == VideoFormat::Rgba,
+        );
+        // a seek or flush jumps to unrelated frames, which is no cut
+        let discont = buffer.flags().contains(gst::BufferFlags::DISCONT);
+        let pts = buffer.pts().map(|pts| Duration::from_nanos(pts.nseconds()));
+        self.compare(histogram, pts, discont);
+    }
+
+    /// Compares the histogram of a frame to the previous one, recording a
+    /// change at `pts` if they differ by at least the threshold.
+    fn compare(&mut self, histogram: [u32; BINS], pts: Option<Duration>, discont: bool) {
+        if let Some(previous) = self.previous.replace(histogram)
+            && !discont
+            && difference(&previous, &histogram) >= self.threshold
+            && let Some(pts) = pts
+        {
+            self.changes.push(pts);
+        }
+    }
+}
+
+/// Counts the luma levels of the sampled pixels of an NV12 (only its Y plane
+/// is read) or RGBA frame.
+fn histogram(data: &[u8], width: usize, height: usize, stride: usize, rgba: bool) -> [u32; BINS] {
+    let mut histogram = [0u32; BINS];
+    for row in data.chunks(stride).take(height).step_by(STEP) {
+        if rgba {
+            for pixel in row.chunks_exact(4).take(width).step_by(STEP) {
+                histogram[luma(pixel[0], pixel[1], pixel[2]) as usize * BINS / 256] += 1;
+            }
+        } else {
+            for &luma in row.iter().take(width).step_by(STEP) {
+                histogram[luma as usize * BINS / 256] += 1;
+            }
+        }
+    }
+    histogram
+}
+
+/// Approximates the BT.709 luma of an RGB pixel.
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    ((54 * r as u32 + 183 * g as u32 + 19 * b as u32) >> 8) as u8
+}
+
+/// The fraction (`0.0..=1.0`) of samples which moved to another bin.
+fn difference(a: &[u32; BINS], b: &[u32; BINS]) -> f64 {
+    let total: u32 = a.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let moved: u32 = a.iter().zip(b).map(|(a, b)| a.abs_diff(*b)).sum();
+    moved as f64 / (2 * total) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: usize = 16;
+    const HEIGHT: usize = 16;
+
+    /// A Y plane whose rows above `split` have luma `top`, the rest `bottom`.
+    fn frame(top: u8, bottom: u8, split: usize) -> Vec<u8> {
+        (0..HEIGHT)
+            .flat_map(|y| [if y < split { top } else { bottom }; WIDTH])
+            .collect()
+    }
+
+    fn push(detector: &mut SceneDetector, frame: &[u8], secs: u64) {
+        let histogram = histogram(frame, WIDTH, HEIGHT, WIDTH, false);
+        detector.compare(histogram, Some(Duration::from_secs(secs)), false);
+    }
+
+    #[test]
+    fn detects_cuts() {
+        let mut detector = SceneDetector::new(0.5);
+        push(&mut detector, &frame(0, 0, 0), 0);
+        push(&mut detector, &frame(0, 0, 0), 1);
+        push(&mut detector, &frame(255, 255, 0), 2);
+        push(&mut detector, &frame(255, 255, 0), 3);
+        assert_eq!(detector.changes, [Duration::from_secs(2)]);
+    }
+
+    #[test]
+    fn ignores_changes_below_threshold() {
+        // half of the frame changes
+        let mut detector = SceneDetector::new(0.6);
+        push(&mut detector, &frame(0, 0, 0), 0);
+        push(&mut detector, &frame(255, 0, HEIGHT / 2), 1);
+        assert!(detector.changes.is_empty());
+
+        let mut detector = SceneDetector::new(0.4);
+        push(&mut detector, &frame(0, 0, 0), 0);
+        push(&mut detector, &frame(255, 0, HEIGHT / 2), 1);
+        assert_eq!(detector.changes, [Duration::from_secs(1)]);
+    }
+
+    #[test]
+    fn clamps_threshold() {
+        let mut detector = SceneDetector::new(2.0);
+        push(&mut detector, &frame(0, 0, 0), 0);
+        push(&mut detector, &frame(255, 0, HEIGHT / 2), 1);
+        push(&mut detector, &frame(0, 0, 0), 2);
+        push(&mut detector, &frame(255, 255, 0), 3);
+        // only a frame which changed completely reaches the clamped threshold
+        assert_eq!(detector.changes, [Duration::from_secs(3)]);
+    }
+
+    #[test]
+    fn ignores_discontinuities() {
+        let mut detector = SceneDetector::new(0.5);
+        push(&mut detector, &frame(0, 0, 0), 0);
+        let white = histogram(&frame(255, 255, 0), WIDTH, HEIGHT, WIDTH, false);
+        detector.compare(white, Some(Duration::from_secs(1)), true);
+        // frames without a timestamp can't be reported either
+        let black = histogram(&frame(0, 0, 0), WIDTH, HEIGHT, WIDTH, false);
+        detector.compare(black, None, false);
+        assert!(detector.changes.is_empty());
+    }
+
+    #[test]
+    fn samples_every_step() {
+        let histogram = histogram(&frame(0, 0, 0), WIDTH, HEIGHT, WIDTH, false);
+        assert_eq!(histogram[0], ((WIDTH / STEP) * (HEIGHT / STEP)) as u32);
+        assert_eq!(histogram.iter().sum::<u32>(), histogram[0]);
+    }
+
+    #[test]
+    fn skips_row_padding() {
+        // rows padded with white past the width
+        let stride = WIDTH + 8;
+        let padded: Vec<u8> = (0..HEIGHT)
+            .flat_map(|_| (0..stride).map(|x| if x < WIDTH { 0 } else { 255 }))
+            .collect();
+        assert_eq!(
+            histogram(&padded, WIDTH, HEIGHT, stride, false),
+            histogram(&frame(0, 0, 0), WIDTH, HEIGHT, WIDTH, false)
+        );
+    }
+
+    #[test]
+    fn reads_rgba_luma() {
+        let gray: Vec<u8> = [128, 128, 128, 255].repeat(WIDTH * HEIGHT);
+        let rgba = histogram(&gray, WIDTH, HEIGHT, WIDTH * 4, true);
+        let nv12 = histogram(&frame(128, 128, 0), WIDTH, HEIGHT, WIDTH, false);
+        assert_eq!(rgba, nv12);
+    }
+}
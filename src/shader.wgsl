@@ -5,6 +5,30 @@ struct VertexOutput {
 
 struct Uniforms {
     rect: vec4<f32>,
+    // each column produces one of R, G and B from YUV
+    yuv2rgb: mat3x3<f32>,
+    // Y offset, Y scale, UV scale
+    yuv_range: vec4<f32>,
+    // solid color drawn instead of the frame (e.g., an inset border) if alpha is non-zero
+    fill: vec4<f32>,
+    // the part of the displayed frame to sample, as left, top, right, bottom
+    crop: vec4<f32>,
+    // parameters of a custom shader's `post_process`
+    custom: array<vec4<f32>, 4>,
+    // maps colors to LUT coordinates, `lut_scale.w` is 1.0 if a LUT is applied
+    lut_scale: vec4<f32>,
+    lut_offset: vec4<f32>,
+    hdr_output: u32,
+    // clockwise quarter turns, plus 4 if flipped horizontally
+    orientation: u32,
+    opacity: f32,
+    // how far the frame has faded in from black
+    fade: f32,
+    // 0 none, 1 sharpen, 2 blur, 3 grayscale, 4 sepia
+    effect: u32,
+    effect_strength: f32,
+    // 1 if the frame is RGBA in `tex_y` rather than NV12
+    packed_rgb: u32,
 }
 
 @group(0) @binding(0)
@@ -19,6 +43,9 @@ var s: sampler;
 @group(0) @binding(3)
 var<uniform> uniforms: Uniforms;
 
+@group(1) @binding(0)
+var lut: texture_3d<f32>;
+
 @vertex
 fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
     var quad = array<vec4<f32>, 6>(
@@ -31,26 +58,90 @@ fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
     );
 
     var out: VertexOutput;
-    out.uv = quad[in_vertex_index].zw;
+    // cropped edges are never sampled, so no clipping layer is needed
+    let uv = mix(uniforms.crop.xy, uniforms.crop.zw, quad[in_vertex_index].zw);
+    switch (uniforms.orientation % 4u) {
+        case 1u: { out.uv = vec2<f32>(uv.y, 1.0 - uv.x); }
+        case 2u: { out.uv = vec2<f32>(1.0 - uv.x, 1.0 - uv.y); }
+        case 3u: { out.uv = vec2<f32>(1.0 - uv.y, uv.x); }
+        default: { out.uv = uv; }
+    }
+    // the frame is flipped before it is rotated
+    if (uniforms.orientation >= 4u) {
+        out.uv.x = 1.0 - out.uv.x;
+    }
     out.position = vec4<f32>(quad[in_vertex_index].xy, 1.0, 1.0);
     return out;
 }
 
+fn sample_rgb(uv: vec2<f32>) -> vec3<f32> {
+    if (uniforms.packed_rgb != 0u) {
+        return textureSample(tex_y, s, uv).rgb;
+    }
+    // coefficients of the negotiated colorimetry (BT.709 limited range by default)
+    var yuv = vec3<f32>(0.0);
+    yuv.x = (textureSample(tex_y, s, uv).r - uniforms.yuv_range.x) * uniforms.yuv_range.y;
+    yuv.y = (textureSample(tex_uv, s, uv).r - 0.5) * uniforms.yuv_range.z;
+    yuv.z = (textureSample(tex_uv, s, uv).g - 0.5) * uniforms.yuv_range.z;
+    return yuv * uniforms.yuv2rgb;
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    // BT.709 precomputed coefficents
-    let yuv2rgb = mat3x3<f32>(
-        1, 0, 1.5748,
-        1, -0.1873, -0.4681,
-        1, 1.8556, 0,
-    );
+    var rgb = sample_rgb(in.uv);
 
-    var yuv = vec3<f32>(0.0);
-    yuv.x = (textureSample(tex_y, s, in.uv).r - 0.0625) / 0.8588;
-    yuv.y = (textureSample(tex_uv, s, in.uv).r - 0.5) / 0.8784;
-    yuv.z = (textureSample(tex_uv, s, in.uv).g - 0.5) / 0.8784;
+    // `effect` is uniform, so sampling within the branches is allowed
+    let texel = 1.0 / vec2<f32>(textureDimensions(tex_y));
+    switch (uniforms.effect) {
+        case 1u: {
+            // unsharp mask against the four direct neighbours
+            let neighbours = (sample_rgb(in.uv + vec2<f32>(texel.x, 0.0))
+                + sample_rgb(in.uv - vec2<f32>(texel.x, 0.0))
+                + sample_rgb(in.uv + vec2<f32>(0.0, texel.y))
+                + sample_rgb(in.uv - vec2<f32>(0.0, texel.y))) * 0.25;
+            rgb = rgb + (rgb - neighbours) * uniforms.effect_strength;
+        }
+        case 2u: {
+            // 3x3 box blur with taps `effect_strength` pixels apart
+            var sum = vec3<f32>(0.0);
+            for (var x = -1; x <= 1; x++) {
+                for (var y = -1; y <= 1; y++) {
+                    sum += sample_rgb(in.uv + vec2<f32>(f32(x), f32(y)) * texel * uniforms.effect_strength);
+                }
+            }
+            rgb = sum / 9.0;
+        }
+        case 3u: {
+            rgb = vec3<f32>(dot(rgb, vec3<f32>(0.2126, 0.7152, 0.0722)));
+        }
+        case 4u: {
+            rgb = vec3<f32>(
+                dot(rgb, vec3<f32>(0.393, 0.769, 0.189)),
+                dot(rgb, vec3<f32>(0.349, 0.686, 0.168)),
+                dot(rgb, vec3<f32>(0.272, 0.534, 0.131)),
+            );
+        }
+        default: {}
+    }
+
+    if (uniforms.lut_scale.w != 0.0) {
+        rgb = textureSample(lut, s, rgb * uniforms.lut_scale.xyz + uniforms.lut_offset.xyz).rgb;
+    }
+
+    // defined after this file, by the custom shader if any
+    rgb = post_process(rgb, in.uv);
+
+    if (uniforms.fill.a > 0.0) {
+        rgb = uniforms.fill.rgb;
+    }
 
-    var rgb = clamp(yuv * yuv2rgb, vec3<f32>(0), vec3<f32>(1));
+    if (uniforms.hdr_output != 0u) {
+        // extended range surfaces are linear; keep super-whites instead of clipping them
+        rgb = pow(max(rgb, vec3<f32>(0)), vec3<f32>(2.4));
+    } else {
+        rgb = clamp(rgb, vec3<f32>(0), vec3<f32>(1));
+    }
 
-    return vec4<f32>(rgb, 1.0);
+    // the output is premultiplied by its opacity
+    return vec4<f32>(rgb * uniforms.fade * uniforms.opacity, uniforms.opacity);
 }
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// The playback state of a [`Video`](crate::Video) which can be saved and
+/// restored later, e.g., to continue watching where the user left off, see
+/// [`Video::snapshot_state`](crate::Video::snapshot_state).
+///
+/// With the `serde` feature, the state can be serialized.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlaybackState {
+    /// The playback position.
+    pub position: Duration,
+    /// Whether playback is paused.
+    pub paused: bool,
+    /// The playback speed.
+    pub speed: f64,
+    /// The volume multiplier of the audio.
+    pub volume: f64,
+    /// Whether the audio is muted.
+    pub muted: bool,
+    /// The index of the selected audio track, if any.
+    pub audio_track: Option<i32>,
+    /// The index of the selected subtitle track, if any.
+    pub text_track: Option<i32>,
+    /// The brightness, see [`Video::set_brightness`](crate::Video::set_brightness).
+    pub brightness: f64,
+    /// The contrast, see [`Video::set_contrast`](crate::Video::set_contrast).
+    pub contrast: f64,
+    /// The hue, see [`Video::set_hue`](crate::Video::set_hue).
+    pub hue: f64,
+    /// The saturation, see [`Video::set_saturation`](crate::Video::set_saturation).
+    pub saturation: f64,
+    /// The gamma, see [`Video::set_gamma`](crate::Video::set_gamma).
+    pub gamma: f64,
+}
@@ -0,0 +1,69 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::time::Duration;
+
+/// Playback statistics, useful to find out why playback stutters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PlaybackStats {
+    /// Frames which reached the video sink in time.
+    pub frames_rendered: u64,
+    /// Frames which were dropped for being late.
+    pub frames_dropped: u64,
+    /// The name of the element decoding the video (e.g., `avdec_h264`), if any.
+    pub decoder: Option<String>,
+    /// The bitrate of the video stream in bits per second, if known.
+    pub bitrate: Option<u32>,
+    /// The largest amount of media queued ahead of the decoders and sinks.
+    pub queue_level: Duration,
+}
+
+impl PlaybackStats {
+    /// The fraction (`0.0..=1.0`) of frames which were dropped.
+    pub fn drop_ratio(&self) -> f64 {
+        let total = self.frames_rendered + self.frames_dropped;
+        if total == 0 {
+            0.0
+        } else {
+            self.frames_dropped as f64 / total as f64
+        }
+    }
+}
+
+/// Gathers the statistics of the video sink, decoder and queues in the pipeline.
+pub(crate) fn collect(pipeline: &gst::Pipeline, video_sink: &gst_app::AppSink) -> PlaybackStats {
+    let mut stats = PlaybackStats::default();
+
+    let sink_stats = video_sink.property::<gst::Structure>("stats");
+    stats.frames_rendered = sink_stats.get::<u64>("rendered").unwrap_or(0);
+    stats.frames_dropped = sink_stats.get::<u64>("dropped").unwrap_or(0);
+
+    for element in pipeline.iterate_recurse().into_iter().flatten() {
+        let Some(factory) = element.factory() else {
+            continue;
+        };
+        let is_video_decoder = factory
+            .metadata(gst::ELEMENT_METADATA_KLASS)
+            .is_some_and(|klass| klass.contains("Decoder") && klass.contains("Video"));
+        if stats.decoder.is_none() && is_video_decoder {
+            stats.decoder = Some(factory.name().to_string());
+        }
+        if matches!(factory.name().as_str(), "queue" | "queue2") {
+            let level = Duration::from_nanos(element.property::<u64>("current-level-time"));
+            stats.queue_level = stats.queue_level.max(level);
+        }
+    }
+
+    if pipeline.has_property("current-video", None) {
+        let id = pipeline.property::<i32>("current-video").max(0);
+        stats.bitrate = pipeline
+            .emit_by_name::<Option<gst::TagList>>("get-video-tags", &[&id])
+            .and_then(|tags| {
+                tags.get::<gst::tags::Bitrate>()
+                    .or_else(|| tags.get::<gst::tags::NominalBitrate>())
+                    .map(|bitrate| bitrate.get())
+            });
+    }
+
+    stats
+}
@@ -0,0 +1,394 @@
+use crate::{Error, PipelineError};
+use gstreamer as gst;
+use gstreamer_app as gst_app;
+use gstreamer_app::prelude::*;
+use iced::widget::text;
+use std::time::Duration;
+
+/// A subtitle with its timing, as taken from the subtitle stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtitleCue {
+    /// The text of the subtitle. ASS/SSA subtitles keep their override tags,
+    /// see [`StyledSubtitle::parse_ass`].
+    pub text: String,
+    /// The position in the media the subtitle is shown at.
+    ///
+    /// **Note:** This does not include the offset set with
+    /// [`Video::set_text_offset`](crate::Video::set_text_offset).
+    pub start: Duration,
+    /// How long the subtitle is shown for.
+    pub duration: Duration,
+}
+
+impl SubtitleCue {
+    /// The position in the media the subtitle is hidden at.
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// Decodes every cue of the subtitle file at `url` up front, with a
+/// pipeline separate from playback.
+pub(crate) fn load_cues(url: &url::Url) -> Result<Vec<SubtitleCue>, Error> {
+    let pipeline = gst::parse::launch(&format!(
+        "uridecodebin uri=\"{}\" ! appsink name=iced_cues sync=false",
+        url.as_str()
+    ))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| Error::Cast("gst::Pipeline"))?;
+    let sink = pipeline
+        .by_name("iced_cues")
+        .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+        .ok_or_else(|| Error::AppSink("iced_cues".into()))?;
+
+    pipeline.set_state(gst::State::Playing)?;
+
+    let mut cues = Vec::new();
+    // pulling fails once the end of the file is reached, or on errors
+    while let Ok(sample) = sink.pull_sample() {
+        let Some(buffer) = sample.buffer() else {
+            continue;
+        };
+        let (Some(pts), Ok(map)) = (buffer.pts(), buffer.map_readable()) else {
+            continue;
+        };
+        cues.push(SubtitleCue {
+            text: String::from_utf8_lossy(map.as_slice()).into_owned(),
+            start: Duration::from_nanos(pts.nseconds()),
+            duration: Duration::from_nanos(buffer.duration().map_or(0, |d| d.nseconds())),
+        });
+    }
+
+    let error = pipeline.bus().and_then(|bus| PipelineError::from_bus(&bus));
+    pipeline.set_state(gst::State::Null)?;
+    match error {
+        Some(error) => Err(error.into()),
+        None => {
+            // overlapping cues may not be decoded in order
+            cues.sort_by_key(|cue| cue.start);
+            Ok(cues)
+        }
+    }
+}
+
+/// Get the first of the sorted `cues` which starts after `position`.
+pub(crate) fn next_cue(cues: &[SubtitleCue], position: Duration) -> Option<&SubtitleCue> {
+    cues.iter().find(|cue| cue.start > position)
+}
+
+/// Get the last of the sorted `cues` which started more than `grace` before `position`.
+pub(crate) fn previous_cue(
+    cues: &[SubtitleCue],
+    position: Duration,
+    grace: Duration,
+) -> Option<&SubtitleCue> {
+    cues.iter().rev().find(|cue| cue.start + grace < position)
+}
+
+/// A run of subtitle text sharing the same style.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SubtitleSpan {
+    /// The text of the span.
+    pub text: String,
+    /// Whether the span is bold.
+    pub bold: bool,
+    /// Whether the span is italic.
+    pub italic: bool,
+    /// Whether the span is underlined.
+    pub underline: bool,
+    /// Whether the span is struck through.
+    pub strikethrough: bool,
+    /// The primary color of the span, if overridden.
+    pub color: Option<iced::Color>,
+    /// The font family of the span, if overridden.
+    pub font: Option<String>,
+    /// The font size of the span in script pixels, if overridden.
+    pub size: Option<f32>,
+}
+
+/// A subtitle event with the styling of its ASS/SSA override tags preserved.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StyledSubtitle {
+    /// The styled runs of text. Line breaks are kept as `\n`.
+    pub spans: Vec<SubtitleSpan>,
+    /// The alignment on the screen, as on a numpad (e.g., `2` is bottom center), if overridden.
+    pub alignment: Option<u8>,
+    /// The position of the subtitle in script pixels, if overridden.
+    pub position: Option<(f32, f32)>,
+}
+
+impl StyledSubtitle {
+    /// Parses an ASS/SSA dialogue event, either as just its text or as the
+    /// `ReadOrder,Layer,Style,Name,MarginL,MarginR,MarginV,Effect,Text` fields
+    /// of Matroska ASS blocks.
+    pub fn parse_ass(event: &str) -> Self {
+        let text = match event.splitn(9, ',').collect::<Vec<_>>().as_slice() {
+            [read_order, _, _, _, _, _, _, _, text] if read_order.trim().parse::<u64>().is_ok() => {
+                text
+            }
+            _ => event,
+        };
+
+        let mut subtitle = StyledSubtitle::default();
+        let mut style = SubtitleSpan::default();
+        let mut rest = text;
+
+        while !rest.is_empty() {
+            let (plain, after) = match rest.find('{') {
+                Some(start) => rest.split_at(start),
+                None => (rest, ""),
+            };
+            subtitle.push_text(&style, plain);
+
+            let Some(end) = after.find('}') else {
+                // an unterminated block is just text
+                subtitle.push_text(&style, after);
+                break;
+            };
+            // blocks without backslashes are comments
+            for tag in after[1..end].split('\\').skip(1) {
+                subtitle.apply_tag(&mut style, tag.trim());
+            }
+            rest = &after[end + 1..];
+        }
+
+        subtitle
+    }
+
+    /// Wraps subtitle text without any styling.
+    pub fn plain(text: &str) -> Self {
+        StyledSubtitle {
+            spans: vec![SubtitleSpan {
+                text: text.to_owned(),
+                ..SubtitleSpan::default()
+            }],
+            ..StyledSubtitle::default()
+        }
+    }
+
+    /// Returns the text without any styling.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Converts the styled runs into spans for iced's `rich_text`. Font
+    /// families are not carried over, as iced needs them to be `'static`.
+    pub fn to_spans(&self) -> Vec<text::Span<'static>> {
+        self.spans
+            .iter()
+            .map(|span| {
+                let font = iced::Font {
+                    weight: if span.bold {
+                        iced::font::Weight::Bold
+                    } else {
+                        iced::font::Weight::Normal
+                    },
+                    style: if span.italic {
+                        iced::font::Style::Italic
+                    } else {
+                        iced::font::Style::Normal
+                    },
+                    ..iced::Font::default()
+                };
+                text::Span::new(span.text.clone())
+                    .font(font)
+                    .color_maybe(span.color)
+                    .underline(span.underline)
+                    .strikethrough(span.strikethrough)
+            })
+            .collect()
+    }
+
+    fn push_text(&mut self, style: &SubtitleSpan, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let text = text
+            .replace("\\N", "\n")
+            .replace("\\n", "\n")
+            .replace("\\h", "\u{a0}");
+
+        // `style` never holds any text itself, so this compares just the styling
+        match self.spans.last_mut() {
+            Some(last)
+                if SubtitleSpan {
+                    text: String::new(),
+                    ..last.clone()
+                } == *style =>
+            {
+                last.text.push_str(&text);
+            }
+            _ => self.spans.push(SubtitleSpan {
+                text,
+                ..style.clone()
+            }),
+        }
+    }
+
+    fn apply_tag(&mut self, style: &mut SubtitleSpan, tag: &str) {
+        // font names may be any text, so they can't be split like other tags
+        if let Some(font) = tag.strip_prefix("fn") {
+            style.font = Some(font.to_owned()).filter(|font| !font.is_empty());
+            return;
+        }
+
+        // tag names are letters, optionally prefixed by a digit (e.g., `\1c`)
+        let digits = tag.chars().take_while(char::is_ascii_digit).count().min(1);
+        let split = digits
+            + tag[digits..]
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(tag.len() - digits);
+        let (name, value) = tag.split_at(split);
+        let flag = value.trim() != "0";
+
+        match name {
+            "b" => {
+                // `\b` also accepts weights such as 700
+                style.bold = value
+                    .parse::<u32>()
+                    .is_ok_and(|weight| weight == 1 || weight >= 600);
+            }
+            "i" => style.italic = flag,
+            "u" => style.underline = flag,
+            "s" => style.strikethrough = flag,
+            "c" | "1c" => style.color = parse_color(value),
+            "fs" => style.size = value.parse().ok(),
+            "an" => {
+                self.alignment = value
+                    .parse()
+                    .ok()
+                    .filter(|alignment| (1..=9).contains(alignment));
+            }
+            "pos" => {
+                let mut coordinates = value
+                    .trim_matches(['(', ')'])
+                    .split(',')
+                    .filter_map(|coordinate| coordinate.trim().parse().ok());
+                if let (Some(x), Some(y)) = (coordinates.next(), coordinates.next()) {
+                    self.position = Some((x, y));
+                }
+            }
+            // `\r` resets to the default style, `\rName` to a named one
+            name if name.starts_with('r') => *style = SubtitleSpan::default(),
+            // other tags (e.g., borders, animations or karaoke) are ignored
+            _ => {}
+        }
+    }
+}
+
+/// Parses an ASS color, written as `&HBBGGRR&` with an optional alpha byte.
+fn parse_color(value: &str) -> Option<iced::Color> {
+    let hex = value
+        .trim_start_matches('&')
+        .trim_start_matches(['H', 'h'])
+        .trim_end_matches('&');
+    let bgr = u32::from_str_radix(hex, 16).ok()?;
+    Some(iced::Color::from_rgb8(
+        (bgr & 0xff) as u8,
+        ((bgr >> 8) & 0xff) as u8,
+        ((bgr >> 16) & 0xff) as u8,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start: u64, duration: u64) -> SubtitleCue {
+        SubtitleCue {
+            text: format!("{start}"),
+            start: Duration::from_secs(start),
+            duration: Duration::from_secs(duration),
+        }
+    }
+
+    #[test]
+    fn finds_no_cues_in_empty_list() {
+        let position = Duration::from_secs(1);
+        assert_eq!(next_cue(&[], position), None);
+        assert_eq!(previous_cue(&[], position, Duration::ZERO), None);
+    }
+
+    #[test]
+    fn finds_overlapping_cues_by_start() {
+        // the first cue spans the other two
+        let cues = [cue(0, 10), cue(2, 1), cue(5, 1)];
+        let grace = Duration::from_millis(500);
+
+        assert_eq!(next_cue(&cues, Duration::from_secs(1)), Some(&cues[1]));
+        assert_eq!(next_cue(&cues, Duration::from_secs(2)), Some(&cues[2]));
+        assert_eq!(next_cue(&cues, Duration::from_secs(5)), None);
+
+        assert_eq!(
+            previous_cue(&cues, Duration::from_secs(3), grace),
+            Some(&cues[1])
+        );
+        // the current cue only started within the grace period
+        assert_eq!(
+            previous_cue(&cues, Duration::from_millis(2200), grace),
+            Some(&cues[0])
+        );
+        assert_eq!(previous_cue(&cues, Duration::from_millis(400), grace), None);
+    }
+
+    #[test]
+    fn parses_override_tags() {
+        let subtitle = StyledSubtitle::parse_ass("{\\b1\\i1}Bold{\\b0} italic{\\r} plain");
+        assert_eq!(subtitle.plain_text(), "Bold italic plain");
+        assert_eq!(subtitle.spans.len(), 3);
+        assert!(subtitle.spans[0].bold && subtitle.spans[0].italic);
+        assert!(!subtitle.spans[1].bold && subtitle.spans[1].italic);
+        assert_eq!(
+            subtitle.spans[2],
+            SubtitleSpan {
+                text: " plain".into(),
+                ..SubtitleSpan::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_colors_alignment_and_position() {
+        let subtitle = StyledSubtitle::parse_ass("{\\an8\\pos(320, 50)\\c&H0000FF&\\fs32}Red");
+        assert_eq!(subtitle.alignment, Some(8));
+        assert_eq!(subtitle.position, Some((320.0, 50.0)));
+        assert_eq!(
+            subtitle.spans[0].color,
+            Some(iced::Color::from_rgb8(255, 0, 0))
+        );
+        assert_eq!(subtitle.spans[0].size, Some(32.0));
+    }
+
+    #[test]
+    fn parses_matroska_fields() {
+        let subtitle = StyledSubtitle::parse_ass("3,0,Default,,0,0,0,,{\\i1}Hi, there");
+        assert_eq!(subtitle.plain_text(), "Hi, there");
+        assert!(subtitle.spans[0].italic);
+
+        // commas in plain events are part of the text
+        let subtitle = StyledSubtitle::parse_ass("Hello, world");
+        assert_eq!(subtitle.plain_text(), "Hello, world");
+    }
+
+    #[test]
+    fn keeps_line_breaks() {
+        let subtitle = StyledSubtitle::parse_ass("One\\NTwo\\hThree");
+        assert_eq!(subtitle.plain_text(), "One\nTwo\u{a0}Three");
+    }
+
+    #[test]
+    fn ignores_malformed_tags() {
+        let subtitle =
+            StyledSubtitle::parse_ass("{\\an12\\pos(1,)\\c&Hzz&\\fs\\}{\\1\\ü}{comment}Text");
+        assert_eq!(subtitle.alignment, None);
+        assert_eq!(subtitle.position, None);
+        assert_eq!(subtitle.spans, StyledSubtitle::plain("Text").spans);
+    }
+
+    #[test]
+    fn keeps_unterminated_blocks_as_text() {
+        let subtitle = StyledSubtitle::parse_ass("a{\\i1b");
+        assert_eq!(subtitle.plain_text(), "a{\\i1b");
+        assert!(!subtitle.spans[0].italic);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::Error;
+use crate::video::{Position, Video};
+use iced::widget::image as img;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Thumbnails packed into a single image, see [`Video::thumbnail_sprite_sheet`].
+#[derive(Debug, Clone)]
+pub struct SpriteSheet {
+    /// The image holding every thumbnail.
+    pub image: img::Handle,
+    /// The area of each thumbnail within the image in pixels, in the order of
+    /// the positions they were generated for.
+    pub tiles: Vec<iced::Rectangle<u32>>,
+}
+
+impl SpriteSheet {
+    /// Packs RGBA thumbnails of `size` in rows of `columns`.
+    pub(crate) fn pack(size: iced::Size<u32>, columns: u32, thumbnails: &[Vec<u8>]) -> Self {
+        let count = thumbnails.len() as u32;
+        let columns = columns.min(count).max(1);
+        let rows = count.div_ceil(columns);
+        let sheet_width = size.width * columns;
+        let row_bytes = size.width as usize * 4;
+
+        let mut rgba = vec![0; (sheet_width * size.height * rows) as usize * 4];
+        let tiles = thumbnails
+            .iter()
+            .enumerate()
+            .map(|(index, thumbnail)| {
+                let tile = iced::Rectangle {
+                    x: (index as u32 % columns) * size.width,
+                    y: (index as u32 / columns) * size.height,
+                    width: size.width,
+                    height: size.height,
+                };
+                for (y, row) in thumbnail.chunks_exact(row_bytes).enumerate() {
+                    let start =
+                        ((tile.y as usize + y) * sheet_width as usize + tile.x as usize) * 4;
+                    rgba[start..start + row_bytes].copy_from_slice(row);
+                }
+                tile
+            })
+            .collect();
+
+        SpriteSheet {
+            image: img::Handle::from_rgba(sheet_width, size.height * rows, rgba),
+            tiles,
+        }
+    }
+}
+
+/// Cancels thumbnail generation of an [`AdaptiveThumbnails`], possibly from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Stops the current [`AdaptiveThumbnails::generate`] call after the
+    /// thumbnail in progress and discards all pending positions.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether cancellation was requested and not yet handled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Thumbnails of long media which start out coarse and are refined around the
+/// regions the user hovers or seeks to, rather than being evenly spaced.
+///
+/// Thumbnails are generated in small batches with [`AdaptiveThumbnails::generate`],
+/// e.g., whenever the application is idle.
+#[derive(Debug)]
+pub struct AdaptiveThumbnails {
+    duration: Duration,
+    size: iced::Size<u32>,
+    min_spacing: Duration,
+    refine_count: usize,
+    thumbnails: BTreeMap<Duration, img::Handle>,
+    pending: VecDeque<Duration>,
+    cancel: CancelHandle,
+}
+
+impl AdaptiveThumbnails {
+    /// Plans `coarse` evenly spaced thumbnails over the duration of `video`,
+    /// scaled to `size`.
+    pub fn new(video: &Video, coarse: usize, size: iced::Size<u32>) -> Self {
+        let duration = video.duration();
+        let step = duration / (coarse.max(1) as u32);
+        AdaptiveThumbnails {
+            duration,
+            size,
+            min_spacing: Duration::from_secs(1),
+            refine_count: 8,
+            thumbnails: BTreeMap::new(),
+            pending: (0..coarse).map(|i| step * i as u32).collect(),
+            cancel: CancelHandle::default(),
+        }
+    }
+
+    /// Sets the minimum distance between two thumbnails. Defaults to one second.
+    pub fn min_spacing(self, min_spacing: Duration) -> Self {
+        AdaptiveThumbnails {
+            min_spacing,
+            ..self
+        }
+    }
+
+    /// Sets how many thumbnails [`AdaptiveThumbnails::refine_around`] adds to
+    /// a region. Defaults to 8.
+    pub fn refine_count(self, refine_count: usize) -> Self {
+        AdaptiveThumbnails {
+            refine_count,
+            ..self
+        }
+    }
+
+    /// Returns a handle which cancels generation, see [`CancelHandle::cancel`].
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Queues denser thumbnails within `window` around `position` (e.g., where
+    /// the user hovers the seek bar), ahead of any other pending thumbnails.
+    ///
+    /// Positions closest to `position` are generated first.
+    pub fn refine_around(&mut self, position: Duration, window: Duration) {
+        // a zero spacing would never advance past `start`
+        let spacing = (window / self.refine_count.max(1) as u32)
+            .max(self.min_spacing)
+            .max(Duration::from_millis(1));
+        let position = position.min(self.duration);
+        let start = position.saturating_sub(window / 2);
+        let end = (position + window / 2).min(self.duration);
+
+        let mut positions: Vec<_> = std::iter::successors(Some(start), |&at| {
+            Some(at + spacing).filter(|&at| at <= end)
+        })
+        .filter(|&at| !self.is_covered(at, spacing / 2))
+        .collect();
+        positions.sort_by_key(|&at| at.abs_diff(position));
+
+        // refined positions take priority over whatever is still queued
+        self.pending.retain(|at| !positions.contains(at));
+        for at in positions.into_iter().rev() {
+            self.pending.push_front(at);
+        }
+    }
+
+    /// Generates up to `budget` pending thumbnails, returning how many were generated.
+    ///
+    /// Thumbnails are decoded without affecting playback, see [`Video::thumbnails`].
+    pub fn generate(&mut self, video: &Video, budget: usize) -> Result<usize, Error> {
+        if self.cancel.0.swap(false, Ordering::SeqCst) {
+            self.pending.clear();
+            return Ok(0);
+        }
+
+        let batch: Vec<_> = self
+            .pending
+            .drain(..budget.min(self.pending.len()))
+            .collect();
+        let handles = video.thumbnails_until(
+            batch.iter().copied().map(Position::Time),
+            self.size,
+            &self.cancel.0,
+        )?;
+
+        let generated = handles.len();
+        self.thumbnails.extend(batch.into_iter().zip(handles));
+
+        if self.cancel.0.swap(false, Ordering::SeqCst) {
+            self.pending.clear();
+        }
+
+        Ok(generated)
+    }
+
+    /// Returns whether there are thumbnails left to generate.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Returns the generated thumbnail closest to `position`, if any.
+    pub fn nearest(&self, position: Duration) -> Option<&img::Handle> {
+        let before = self.thumbnails.range(..=position).next_back();
+        let after = self.thumbnails.range(position..).next();
+        match (before, after) {
+            (Some(before), Some(after)) => Some(if position - *before.0 <= *after.0 - position {
+                before.1
+            } else {
+                after.1
+            }),
+            (Some((_, handle)), None) | (None, Some((_, handle))) => Some(handle),
+            (None, None) => None,
+        }
+    }
+
+    /// Iterates over the generated thumbnails and their positions, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (Duration, &img::Handle)> {
+        self.thumbnails.iter().map(|(&at, handle)| (at, handle))
+    }
+
+    fn is_covered(&self, at: Duration, tolerance: Duration) -> bool {
+        let start = at.saturating_sub(tolerance);
+        self.thumbnails
+            .range(start..=at + tolerance)
+            .next()
+            .is_some()
+            || self
+                .pending
+                .iter()
+                .any(|&pending| pending.abs_diff(at) <= tolerance)
+    }
+}
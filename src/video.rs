@@ -1,16 +1,110 @@
-use crate::Error;
+use crate::adaptive::{self, Quality};
+use crate::builder::VideoBuilder;
+use crate::cache::{self, ThumbnailCache};
+use crate::capture::{self, CaptureDevice};
+use crate::events::{Event, Subscribers};
+use crate::frames::Frames;
+use crate::handle::VideoHandle;
+use crate::health::{self, StreamHealth};
+use crate::language::primary_language;
+use crate::level::SilenceDetector;
+use crate::lut::CubeLut;
+use crate::pipeline::DynamicRange;
+use crate::recording::{self, ExportPreset, Recorder, RecordingSettings};
+use crate::scene::SceneDetector;
+use crate::state::PlaybackState;
+use crate::stats::{self, PlaybackStats};
+use crate::subtitle::{self, SubtitleCue};
+use crate::thumbnail::{AdaptiveThumbnails, SpriteSheet};
+use crate::{Error, MissingPlugin, PipelineError};
 use glib::FlagsClass;
 use gstreamer as gst;
 use gstreamer_app as gst_app;
 use gstreamer_app::prelude::*;
-use gstreamer_video::VideoMeta;
+use gstreamer_video::{VideoColorMatrix, VideoColorRange, VideoFormat, VideoInfo, VideoMeta};
+use iced::futures::channel::oneshot;
 use iced::widget::image as img;
-use std::num::NonZeroU8;
+use std::num::NonZeroU32;
 use std::ops::{Deref, DerefMut};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
+/// How the audio channels are mixed down before playback, see [`Video::set_audio_downmix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DownmixMode {
+    /// The channels of the media are played as they are.
+    #[default]
+    Off,
+    /// Surround audio (e.g., 5.1) is mixed into two channels.
+    Stereo,
+    /// All channels are mixed into one.
+    Mono,
+}
+
+/// Where a seek lands relative to the keyframes of the media, see [`SeekOptions::snap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Snap {
+    /// The keyframe at or before the position.
+    Before,
+    /// The keyframe at or after the position.
+    After,
+    /// The keyframe closest to the position.
+    Nearest,
+}
+
+/// How [`Video::seek_with`] jumps to a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeekOptions {
+    /// Whether the seek lands exactly on the position, which is slower as the
+    /// frames since the previous keyframe are decoded.
+    pub accurate: bool,
+    /// Whether the seek snaps to a keyframe near the position, which is fast.
+    /// Combined with `accurate`, the keyframe closest to the position is used.
+    pub snap: Option<Snap>,
+    /// Whether only keyframes are decoded and shown after the seek (e.g., for
+    /// fast forwarding), until the next seek without it.
+    pub key_units_only: bool,
+    /// Whether queued data is discarded so that the seek takes effect right away.
+    /// Enabled by default.
+    pub flush: bool,
+}
+
+impl Default for SeekOptions {
+    fn default() -> Self {
+        SeekOptions {
+            accurate: false,
+            snap: None,
+            key_units_only: false,
+            flush: true,
+        }
+    }
+}
+
+impl SeekOptions {
+    pub(crate) fn flags(&self) -> gst::SeekFlags {
+        let mut flags = gst::SeekFlags::empty();
+        if self.flush {
+            flags |= gst::SeekFlags::FLUSH;
+        }
+        if self.accurate {
+            flags |= gst::SeekFlags::ACCURATE;
+        }
+        if let Some(snap) = self.snap {
+            flags |= gst::SeekFlags::KEY_UNIT
+                | match snap {
+                    Snap::Before => gst::SeekFlags::SNAP_BEFORE,
+                    Snap::After => gst::SeekFlags::SNAP_AFTER,
+                    Snap::Nearest => gst::SeekFlags::SNAP_NEAREST,
+                };
+        }
+        if self.key_units_only {
+            flags |= gst::SeekFlags::TRICKMODE | gst::SeekFlags::TRICKMODE_KEY_UNITS;
+        }
+        flags
+    }
+}
+
 /// Position in the media.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Position {
@@ -43,6 +137,292 @@ impl From<u64> for Position {
     }
 }
 
+/// The playback status of a [`Video`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlaybackStatus {
+    /// Frames are being presented.
+    Playing,
+    /// Playback was paused, or the end of the stream was reached.
+    Paused,
+    /// Playback is waiting for network data to be buffered.
+    Buffering,
+    /// Playback should be progressing, but no frames arrived recently
+    /// (e.g., a live source stopped sending data).
+    Stalled,
+}
+
+/// How the frames of a video are meant to be displayed, as tagged by the
+/// container (e.g., videos recorded in portrait on a phone).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    /// Displayed as decoded.
+    #[default]
+    Rotate0,
+    /// Rotated 90 degrees clockwise.
+    Rotate90,
+    /// Rotated 180 degrees.
+    Rotate180,
+    /// Rotated 270 degrees clockwise.
+    Rotate270,
+    /// Flipped horizontally.
+    FlipRotate0,
+    /// Flipped horizontally, then rotated 90 degrees clockwise.
+    FlipRotate90,
+    /// Flipped horizontally, then rotated 180 degrees.
+    FlipRotate180,
+    /// Flipped horizontally, then rotated 270 degrees clockwise.
+    FlipRotate270,
+}
+
+impl Orientation {
+    /// Parses the value of GStreamer's `image-orientation` tag (e.g., `rotate-90`).
+    pub(crate) fn from_tag(tag: &str) -> Option<Self> {
+        Some(match tag {
+            "rotate-0" => Orientation::Rotate0,
+            "rotate-90" => Orientation::Rotate90,
+            "rotate-180" => Orientation::Rotate180,
+            "rotate-270" => Orientation::Rotate270,
+            "flip-rotate-0" => Orientation::FlipRotate0,
+            "flip-rotate-90" => Orientation::FlipRotate90,
+            "flip-rotate-180" => Orientation::FlipRotate180,
+            "flip-rotate-270" => Orientation::FlipRotate270,
+            _ => return None,
+        })
+    }
+
+    /// Reads the orientation from a tag list, if it is tagged.
+    pub(crate) fn from_tags(tags: &gst::TagListRef) -> Option<Self> {
+        Self::from_tag(tags.get::<gst::tags::ImageOrientation>()?.get())
+    }
+
+    /// Returns whether width and height are swapped when displayed.
+    pub fn is_transposed(self) -> bool {
+        matches!(
+            self,
+            Orientation::Rotate90
+                | Orientation::Rotate270
+                | Orientation::FlipRotate90
+                | Orientation::FlipRotate270
+        )
+    }
+
+    /// Encodes the orientation for the shader; clockwise quarter turns, plus 4 if flipped.
+    pub(crate) fn shader_value(self) -> u32 {
+        self as u32
+    }
+}
+
+/// A post-processing effect applied to the frame on the GPU, see [`Video::set_effect`].
+///
+/// Unlike [`VideoFilters`], effects don't add elements to the pipeline, and so
+/// don't add any latency.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Effect {
+    /// The frame is drawn as decoded.
+    #[default]
+    None,
+    /// Sharpens edges by the given amount, e.g., `0.5`.
+    Sharpen(f32),
+    /// Blurs over the given radius in pixels of the frame.
+    Blur(f32),
+    /// Drops the colors, keeping only the luminance.
+    Grayscale,
+    /// Tints the frame in brown tones, like an old photograph.
+    Sepia,
+}
+
+impl Effect {
+    pub(crate) fn shader_value(self) -> u32 {
+        match self {
+            Effect::None => 0,
+            Effect::Sharpen(_) => 1,
+            Effect::Blur(_) => 2,
+            Effect::Grayscale => 3,
+            Effect::Sepia => 4,
+        }
+    }
+
+    pub(crate) fn strength(self) -> f32 {
+        match self {
+            Effect::Sharpen(amount) => amount.max(0.0),
+            Effect::Blur(radius) => radius.max(0.0),
+            _ => 0.0,
+        }
+    }
+}
+
+/// How [`Video::scrub`] moves through the media while the user drags a seek bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScrubMode {
+    /// Jump to the keyframe nearest to each position, which only needs a single
+    /// frame to be decoded and keeps up with the drag even for large media.
+    #[default]
+    Keyframe,
+    /// Decode up to the exact frame at each position, which may lag behind the drag.
+    Accurate,
+}
+
+/// The pixel format of frames pushed to [`Video::from_frames`], or decoded
+/// for display, see [`VideoBuilder::output_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameFormat {
+    /// Packed 8-bit RGBA, `width * height * 4` bytes.
+    Rgba,
+    /// A full resolution Y plane followed by a half resolution plane of
+    /// interleaved U and V samples, `width * height * 3 / 2` bytes.
+    Nv12,
+}
+
+impl FrameFormat {
+    /// Get the name of the format in GStreamer caps.
+    pub(crate) fn caps_name(self) -> &'static str {
+        match self {
+            FrameFormat::Rgba => "RGBA",
+            FrameFormat::Nv12 => "NV12",
+        }
+    }
+
+    fn from_info(info: &VideoInfo) -> Self {
+        match info.format() {
+            VideoFormat::Rgba => FrameFormat::Rgba,
+            _ => FrameFormat::Nv12,
+        }
+    }
+}
+
+/// What to do with decoded frames when the UI falls behind the pipeline, i.e.,
+/// a frame arrives while the previous one has not been displayed yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LateFramePolicy {
+    /// Replace frames which have not been displayed yet with newer ones,
+    /// always presenting the freshest frame.
+    #[default]
+    DropOldest,
+    /// Keep the frame which is waiting to be displayed and discard newer ones
+    /// until it has been presented.
+    DropNewest,
+    /// Stall the pipeline until every frame has been presented, so that none
+    /// are skipped.
+    ///
+    /// **Note:** Playback does not progress while the video is not drawn.
+    Block,
+}
+
+impl LateFramePolicy {
+    pub(crate) fn load(policy: &AtomicU8) -> Self {
+        match policy.load(Ordering::SeqCst) {
+            1 => LateFramePolicy::DropNewest,
+            2 => LateFramePolicy::Block,
+            _ => LateFramePolicy::DropOldest,
+        }
+    }
+
+    pub(crate) fn store(self, policy: &AtomicU8) {
+        policy.store(
+            match self {
+                LateFramePolicy::DropOldest => 0,
+                LateFramePolicy::DropNewest => 1,
+                LateFramePolicy::Block => 2,
+            },
+            Ordering::SeqCst,
+        );
+    }
+}
+
+/// Whether a frame waits to be displayed, which [`LateFramePolicy::Block`]
+/// waits on from the streaming thread.
+#[derive(Debug)]
+pub(crate) struct PendingFrame {
+    pending: Mutex<bool>,
+    presented: Condvar,
+}
+
+impl PendingFrame {
+    pub(crate) fn new(pending: bool) -> Self {
+        PendingFrame {
+            pending: Mutex::new(pending),
+            presented: Condvar::new(),
+        }
+    }
+
+    pub(crate) fn is_pending(&self) -> bool {
+        self.pending.lock().map(|pending| *pending).unwrap_or(false)
+    }
+
+    /// Marks a new frame as waiting to be displayed.
+    pub(crate) fn set(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending = true;
+        }
+    }
+
+    /// Marks the frame as displayed, returning whether one was waiting.
+    pub(crate) fn take(&self) -> bool {
+        let Ok(mut pending) = self.pending.lock() else {
+            return false;
+        };
+        self.presented.notify_all();
+        std::mem::take(&mut *pending)
+    }
+
+    /// Wakes the streaming thread, e.g., to give up waiting when stopping.
+    pub(crate) fn notify(&self) {
+        let _pending = self.pending.lock();
+        self.presented.notify_all();
+    }
+
+    /// Blocks until the waiting frame is displayed or `cancel` returns `true`.
+    ///
+    /// `cancel` is checked whenever [`PendingFrame::notify`] is called, and
+    /// regularly in case it changes without a notification.
+    pub(crate) fn wait(&self, cancel: impl Fn() -> bool) {
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+        while *pending && !cancel() {
+            pending = match self
+                .presented
+                .wait_timeout(pending, Duration::from_millis(100))
+            {
+                Ok((pending, _)) => pending,
+                Err(_) => return,
+            };
+        }
+    }
+}
+
+/// Length of the audio snippets played while scrubbing, see [`Video::scrub`].
+const SCRUB_SNIPPET: Duration = Duration::from_millis(80);
+
+/// Nominal framerate of audio-only media, so that frame intervals stay finite.
+const AUDIO_ONLY_FRAMERATE: f64 = 30.0;
+
+/// Get the interval between frames of `sample` from its caps, since the
+/// framerate changes along with the media, see [`Video::set_uri`].
+fn frame_interval(sample: &gst::Sample) -> Duration {
+    let framerate = sample
+        .caps()
+        .and_then(|caps| VideoInfo::from_caps(caps).ok())
+        .map(|info| info.fps().numer() as f64 / info.fps().denom() as f64)
+        .filter(|framerate| framerate.is_normal() && *framerate > 0.0)
+        .unwrap_or(AUDIO_ONLY_FRAMERATE);
+    Duration::from_secs_f64(1.0 / framerate)
+}
+
+/// How far into a cue [`Video::seek_previous_subtitle`] still goes to the cue before it.
+const PREVIOUS_CUE_GRACE: Duration = Duration::from_millis(500);
+
+/// Playback state saved while the user is scrubbing through the media.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Scrub {
+    was_paused: bool,
+    was_muted: bool,
+    /// A snippet is currently playing until its segment is done.
+    playing: bool,
+    /// The position to play next, once the current snippet is done.
+    pending: Option<Duration>,
+}
+
 #[derive(Debug)]
 pub(crate) struct Frame(gst::Sample);
 
@@ -64,6 +444,161 @@ impl Frame {
                 .map(|meta| meta.stride()[0] as u32)
         })
     }
+
+    /// Get the pixel format negotiated for the frame.
+    pub fn format(&self) -> FrameFormat {
+        self.0
+            .caps()
+            .and_then(|caps| VideoInfo::from_caps(caps).ok())
+            .map_or(FrameFormat::Nv12, |info| FrameFormat::from_info(&info))
+    }
+
+    /// Converts the frame to tightly packed RGBA.
+    pub fn rgba(&self) -> Option<Vec<u8>> {
+        let (width, height) = self.size()?;
+        let map = self.readable()?;
+        Some(match self.format() {
+            FrameFormat::Rgba => {
+                let stride = self.stride().unwrap_or(width * 4) as usize;
+                map.chunks(stride)
+                    .take(height as usize)
+                    .flat_map(|row| &row[..width as usize * 4])
+                    .copied()
+                    .collect()
+            }
+            FrameFormat::Nv12 => yuv_to_rgba(
+                map.as_slice(),
+                width,
+                height,
+                1,
+                self.stride(),
+                &self.colorimetry(),
+            ),
+        })
+    }
+
+    /// Get the resolution negotiated for the frame.
+    pub fn size(&self) -> Option<(u32, u32)> {
+        self.0
+            .caps()
+            .and_then(|caps| VideoInfo::from_caps(caps).ok())
+            .map(|info| (info.width(), info.height()))
+    }
+
+    /// Get the colorimetry negotiated for the frame.
+    pub fn colorimetry(&self) -> Colorimetry {
+        self.0
+            .caps()
+            .and_then(|caps| VideoInfo::from_caps(caps).ok())
+            .map(|info| Colorimetry::new(info.colorimetry().matrix(), info.colorimetry().range()))
+            .unwrap_or_default()
+    }
+
+    /// Get the running time at which the frame should be presented.
+    pub fn running_time(&self) -> Option<gst::ClockTime> {
+        let pts = self.0.buffer()?.pts()?;
+        let segment = self.0.segment()?.downcast_ref::<gst::ClockTime>()?;
+        segment.to_running_time(pts)
+    }
+}
+
+/// What the worker thread handled on the bus which is yet to be sent by the
+/// widget, see [`Internal::handle_bus`].
+#[derive(Debug, Default)]
+pub(crate) struct Unreported {
+    /// The media ended, whether or not it looped.
+    pub(crate) ended: bool,
+    /// The media restarted from the beginning.
+    pub(crate) restarted: bool,
+    /// The latest errors, up to [`MAX_UNREPORTED_ERRORS`], as nothing may be
+    /// drawing the video to take them.
+    pub(crate) errors: Vec<PipelineError>,
+}
+
+/// How many errors are kept for the widget, see [`Unreported`].
+const MAX_UNREPORTED_ERRORS: usize = 16;
+
+/// A decoded frame handed to a frame processor, see [`Video::set_frame_processor`].
+#[derive(Debug)]
+pub struct RawFrame<'a> {
+    /// The width of the frame in pixels.
+    pub width: u32,
+    /// The height of the frame in pixels.
+    pub height: u32,
+    /// The number of bytes between the starts of two rows, in both planes of NV12.
+    pub stride: u32,
+    /// The pixel format of `data`, see [`VideoBuilder::output_format`].
+    pub format: FrameFormat,
+    /// The presentation timestamp of the frame.
+    pub pts: Option<Duration>,
+    /// The frame data. For NV12, a full resolution Y plane of `stride * height`
+    /// bytes followed by a half resolution plane of interleaved U and V samples.
+    /// For RGBA, `height` rows of `stride` bytes.
+    pub data: &'a mut [u8],
+}
+
+/// Timing information of a presented frame, see [`VideoPlayer::on_frame_info`](crate::VideoPlayer::on_frame_info).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameInfo {
+    /// The presentation timestamp of the frame, i.e., its position in the media.
+    pub pts: Option<Duration>,
+    /// The running time of the pipeline at which the frame is presented.
+    pub running_time: Option<Duration>,
+    /// The index of the frame from the start of the media, derived from its
+    /// timestamp and the framerate.
+    pub number: Option<u64>,
+}
+
+pub(crate) struct FrameProcessor(Box<dyn FnMut(&mut RawFrame<'_>) + Send>);
+
+impl std::fmt::Debug for FrameProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameProcessor").finish_non_exhaustive()
+    }
+}
+
+impl FrameProcessor {
+    /// Runs the processor over a copy of the sample's buffer.
+    fn process(&mut self, sample: gst::Sample) -> gst::Sample {
+        let Some(info) = sample
+            .caps()
+            .and_then(|caps| VideoInfo::from_caps(caps).ok())
+        else {
+            return sample;
+        };
+        let Some(mut buffer) = sample.buffer_owned() else {
+            return sample;
+        };
+        let stride = Frame(sample.clone())
+            .stride()
+            .unwrap_or(info.stride()[0] as u32);
+
+        {
+            // the sample still holds a reference, so this copies the buffer
+            let buffer = buffer.make_mut();
+            let pts = buffer.pts().map(|pts| Duration::from_nanos(pts.nseconds()));
+            let Ok(mut map) = buffer.map_writable() else {
+                return sample;
+            };
+            (self.0)(&mut RawFrame {
+                width: info.width(),
+                height: info.height(),
+                stride,
+                format: FrameFormat::from_info(&info),
+                pts,
+                data: map.as_mut_slice(),
+            });
+        }
+
+        let mut builder = gst::Sample::builder().buffer(&buffer);
+        if let Some(caps) = sample.caps() {
+            builder = builder.caps(caps);
+        }
+        if let Some(segment) = sample.segment() {
+            builder = builder.segment(segment);
+        }
+        builder.build()
+    }
 }
 
 #[derive(Debug)]
@@ -123,47 +658,265 @@ pub(crate) struct Internal {
     pub(crate) id: u64,
 
     pub(crate) bus: gst::Bus,
+    /// The streams of the media when played by `playbin3`, which replace the
+    /// track properties and signals of `playbin`.
+    pub(crate) streams: Option<gst::StreamCollection>,
+    /// The ids of the streams `playbin3` selected from `streams`.
+    pub(crate) selected_streams: Vec<String>,
     pub(crate) source: gst::Pipeline,
+    pub(crate) video_sink: gst_app::AppSink,
     pub(crate) video_filters: VideoFilters,
     pub(crate) alive: Arc<AtomicBool>,
+    /// Whether the video keeps its texture on the GPU, see [`Video::suspend`].
+    pub(crate) resident: Arc<AtomicBool>,
+    /// The state to restore once resumed, if suspended.
+    pub(crate) suspended: Option<Suspended>,
+    /// The volume fade in progress, see [`Video::fade_volume_to`].
+    pub(crate) volume_fade: Arc<Mutex<Option<VolumeFade>>>,
+    /// Handles the end of the stream and errors, see [`Internal::handle_bus`].
+    /// Frames are delivered by the callbacks of the video sink instead.
     pub(crate) worker: Option<std::thread::JoinHandle<()>>,
 
     pub(crate) width: i32,
     pub(crate) height: i32,
+    pub(crate) has_video: bool,
+    pub(crate) pixel_aspect_ratio: gst::Fraction,
+    /// The resolution and pixel aspect ratio of the latest frame.
+    pub(crate) frame_size: Arc<Mutex<(i32, i32, gst::Fraction)>>,
+    pub(crate) orientation: Orientation,
+    pub(crate) effect: Effect,
+    pub(crate) lut: Option<CubeLut>,
+    pub(crate) thumbnail_cache: Option<ThumbnailCache>,
+    pub(crate) cover_art: Option<gst::Sample>,
     pub(crate) framerate: f64,
     pub(crate) duration: Duration,
     pub(crate) speed: f64,
     pub(crate) sync_av: bool,
+    /// The offset set by the user, see [`Video::set_av_offset`].
+    pub(crate) user_av_offset: i64,
+    pub(crate) is_live: bool,
 
     pub(crate) hard_volumne: bool,
+    pub(crate) downmix: DownmixMode,
+    pub(crate) silence: Option<SilenceDetector>,
+
+    pub(crate) preferred_languages: Vec<String>,
+    pub(crate) prefer_sdh: bool,
+
+    pub(crate) manifest: Arc<Mutex<Vec<u8>>>,
+    pub(crate) quality: Option<Quality>,
 
     pub(crate) frame: Arc<Mutex<Frame>>,
-    pub(crate) upload_frame: Arc<AtomicBool>,
+    pub(crate) first_frame: Arc<Mutex<Option<gst::Sample>>>,
+    pub(crate) upload_frame: Arc<PendingFrame>,
+    pub(crate) frame_version: Arc<AtomicU64>,
+    /// The latest frame converted to RGBA, keyed by its `frame_version`.
+    pub(crate) frame_image: Mutex<Option<(u64, img::Handle)>>,
+    pub(crate) output_range: Arc<AtomicU8>,
     pub(crate) last_frame_time: Arc<Mutex<Instant>>,
     pub(crate) looping: bool,
+    /// Whether looping wraps around with segment seeks rather than restarting
+    /// the stream once it ended, which leaves no gap.
+    pub(crate) segment_looping: bool,
+    /// How many more times the media loops, or `None` for indefinitely.
+    pub(crate) loops_remaining: Option<u32>,
     pub(crate) is_eos: bool,
     pub(crate) restart_stream: bool,
+    pub(crate) unreported: Unreported,
     pub(crate) sync_av_avg: u64,
     pub(crate) sync_av_counter: u64,
-
-    pub(crate) subtitle_text: Arc<Mutex<Option<String>>>,
+    pub(crate) low_latency: Arc<AtomicBool>,
+    pub(crate) late_frame_policy: Arc<AtomicU8>,
+    pub(crate) frame_processor: Arc<Mutex<Option<FrameProcessor>>>,
+    pub(crate) recorder: Arc<Mutex<Option<Recorder>>>,
+    pub(crate) events: Subscribers,
+    pub(crate) scene_detector: Arc<Mutex<Option<SceneDetector>>>,
+    pub(crate) gates: Arc<Mutex<Vec<Duration>>>,
+    pub(crate) awaiting_approval: Arc<AtomicBool>,
+    pub(crate) latency_estimate: Option<Duration>,
+    pub(crate) buffering: bool,
+    pub(crate) chapters: Vec<Chapter>,
+    pub(crate) audio_scrubbing: bool,
+    pub(crate) scrub_mode: ScrubMode,
+    pub(crate) scrub: Option<Scrub>,
+
+    pub(crate) subtitle_cue: Arc<Mutex<Option<SubtitleCue>>>,
+    pub(crate) subtitle_cues: Option<Vec<SubtitleCue>>,
     pub(crate) upload_text: Arc<AtomicBool>,
+    pub(crate) subtitle_is_ass: Arc<AtomicBool>,
+}
+
+/// A gradual change of the volume, see [`Video::fade_volume_to`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VolumeFade {
+    from: f64,
+    to: f64,
+    start: Instant,
+    duration: Duration,
+}
+
+impl VolumeFade {
+    /// Get the volume at `now`, and whether the fade is complete.
+    fn volume_at(&self, now: Instant) -> (f64, bool) {
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            (now.saturating_duration_since(self.start).as_secs_f64() / self.duration.as_secs_f64())
+                .min(1.0)
+        };
+        (
+            self.from + (self.to - self.from) * progress,
+            progress >= 1.0,
+        )
+    }
+}
+
+/// Sets the volume of playbin, which unmutes it unless restored.
+fn set_volume(pipeline: &gst::Pipeline, volume: f64) {
+    let muted = pipeline.property::<bool>("mute");
+    pipeline.set_property("volume", volume);
+    pipeline.set_property("mute", muted);
+}
+
+/// What [`Video::resume`] restores of a suspended video.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Suspended {
+    position: Duration,
+    paused: bool,
+    audio: i32,
+    text: i32,
 }
 
 impl Internal {
+    /// Stops the pipeline and joins the worker thread. Does nothing once shut down.
+    fn shutdown(&mut self) -> Result<(), Error> {
+        self.alive.store(false, Ordering::SeqCst);
+        self.resident.store(false, Ordering::SeqCst);
+        self.upload_frame.notify();
+        // the bus drops messages once the pipeline is stopped, in which case the
+        // worker notices on its next poll
+        self.wake_worker();
+        let state = self.source.set_state(gst::State::Null);
+
+        if let Some(worker) = self.worker.take()
+            && let Err(err) = worker.join()
+        {
+            match err.downcast_ref::<String>() {
+                Some(e) => log::error!("Video thread panicked: {e}"),
+                None => log::error!("Video thread panicked with unknown reason"),
+            }
+            return Err(Error::Thread);
+        }
+        state?;
+        Ok(())
+    }
+
+    /// Refreshes what is known about the media once a new URI prerolled, see [`Video::set_uri`].
+    fn load_media_info(&mut self) -> Result<(), Error> {
+        let bus = self.source.bus();
+        let pop = |kind| bus.as_ref().and_then(|bus| bus.pop_filtered(&[kind]));
+        self.streams = pop(gst::MessageType::StreamCollection).and_then(|msg| match msg.view() {
+            gst::MessageView::StreamCollection(collection) => Some(collection.stream_collection()),
+            _ => None,
+        });
+        self.selected_streams = pop(gst::MessageType::StreamsSelected)
+            .and_then(|msg| match msg.view() {
+                gst::MessageView::StreamsSelected(selected) => Some(stream_ids(selected)),
+                _ => None,
+            })
+            .unwrap_or_default();
+        self.chapters = pop(gst::MessageType::Toc)
+            .and_then(|msg| match msg.view() {
+                gst::MessageView::Toc(toc) => Some(toc_chapters(&toc.toc().0)),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let caps = self
+            .video_sink
+            .pads()
+            .first()
+            .and_then(|pad| pad.current_caps());
+        self.has_video = caps.is_some()
+            || match &self.streams {
+                Some(streams) => streams
+                    .iter()
+                    .any(|stream| stream.stream_type().contains(gst::StreamType::VIDEO)),
+                None => self.source.property::<i32>("n-video") > 0,
+            };
+
+        let info = caps.and_then(|caps| VideoInfo::from_caps(&caps).ok());
+        self.cover_art = if self.has_video {
+            None
+        } else {
+            attached_picture(&self.source).and_then(|picture| {
+                decode_picture(&picture)
+                    .inspect_err(|err| log::warn!("failed to decode cover art: {err}"))
+                    .ok()
+            })
+        };
+        let info = info.or_else(|| {
+            self.cover_art
+                .as_ref()
+                .and_then(|art| art.caps())
+                .and_then(|caps| VideoInfo::from_caps(caps).ok())
+        });
+        (self.width, self.height, self.pixel_aspect_ratio) = info
+            .as_ref()
+            .map_or((0, 0, gst::Fraction::new(1, 1)), |info| {
+                (info.width() as i32, info.height() as i32, info.par())
+            });
+        *self.frame_size.lock().map_err(|_| Error::Lock)? =
+            (self.width, self.height, self.pixel_aspect_ratio);
+        self.framerate = info
+            .filter(|_| self.has_video)
+            .map(|info| info.fps().numer() as f64 / info.fps().denom() as f64)
+            .filter(|framerate| framerate.is_normal() && *framerate > 0.0)
+            .unwrap_or(AUDIO_ONLY_FRAMERATE);
+
+        // the orientation is posted in the tags of the new media
+        self.orientation = Orientation::default();
+        self.duration = Duration::from_nanos(
+            self.source
+                .query_duration::<gst::ClockTime>()
+                .map(|duration| duration.nseconds())
+                .unwrap_or(0),
+        );
+
+        if let Some(art) = self.cover_art.clone() {
+            *self.frame.lock().map_err(|_| Error::Lock)? = Frame(art);
+            self.upload_frame.set();
+            self.frame_version.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
     pub(crate) fn seek(&self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
+        self.seek_with(
+            position,
+            SeekOptions {
+                accurate,
+                ..SeekOptions::default()
+            },
+        )
+    }
+
+    pub(crate) fn seek_with(
+        &self,
+        position: impl Into<Position>,
+        options: SeekOptions,
+    ) -> Result<(), Error> {
         let position = position.into();
+        let mut flags = options.flags();
+        if self.segment_looping && self.speed > 0.0 {
+            flags |= gst::SeekFlags::SEGMENT;
+        }
 
         // gstreamer complains if the start & end value types aren't the same
         match &position {
             Position::Time(_) => self.source.seek(
                 self.speed,
-                gst::SeekFlags::FLUSH
-                    | if accurate {
-                        gst::SeekFlags::ACCURATE
-                    } else {
-                        gst::SeekFlags::empty()
-                    },
+                flags,
                 gst::SeekType::Set,
                 gst::GenericFormattedValue::from(position),
                 gst::SeekType::Set,
@@ -171,12 +924,7 @@ impl Internal {
             )?,
             Position::Frame(_) => self.source.seek(
                 self.speed,
-                gst::SeekFlags::FLUSH
-                    | if accurate {
-                        gst::SeekFlags::ACCURATE
-                    } else {
-                        gst::SeekFlags::empty()
-                    },
+                flags,
                 gst::SeekType::Set,
                 gst::GenericFormattedValue::from(position),
                 gst::SeekType::Set,
@@ -184,20 +932,135 @@ impl Internal {
             )?,
         };
 
-        *self.subtitle_text.lock().expect("lock subtitle_text") = None;
+        *self.subtitle_cue.lock().expect("lock subtitle_cue") = None;
+        self.upload_text.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    pub(crate) fn seek_keyframe(&self, position: Duration) -> Result<(), Error> {
+        self.source.seek(
+            self.speed,
+            gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT | gst::SeekFlags::SNAP_NEAREST,
+            gst::SeekType::Set,
+            gst::ClockTime::from_nseconds(position.as_nanos() as _),
+            gst::SeekType::Set,
+            gst::ClockTime::NONE,
+        )?;
+
+        *self.subtitle_cue.lock().expect("lock subtitle_cue") = None;
         self.upload_text.store(true, Ordering::SeqCst);
 
         Ok(())
     }
 
+    /// Plays a short snippet starting at `position` and posts `SEGMENT_DONE` when it ends.
+    /// Non-flushing seeks are queued behind the snippet currently playing.
+    fn seek_snippet(&self, position: Duration, flush: bool) -> Result<(), Error> {
+        let start = gst::ClockTime::from_nseconds(position.as_nanos() as _);
+        let stop = gst::ClockTime::from_nseconds((position + SCRUB_SNIPPET).as_nanos() as _);
+        let mut flags = gst::SeekFlags::SEGMENT | gst::SeekFlags::ACCURATE;
+        if flush {
+            flags |= gst::SeekFlags::FLUSH;
+        }
+        self.source.seek(
+            1.0,
+            flags,
+            gst::SeekType::Set,
+            start,
+            gst::SeekType::Set,
+            stop,
+        )?;
+        Ok(())
+    }
+
+    pub(crate) fn scrub(&mut self, position: Duration) -> Result<(), Error> {
+        let scrub = self.scrub.get_or_insert(Scrub {
+            was_paused: self.source.state(gst::ClockTime::ZERO).1 == gst::State::Paused,
+            was_muted: self.source.property("mute"),
+            playing: false,
+            pending: None,
+        });
+
+        if !self.audio_scrubbing {
+            // keep audio from blipping while the position jumps around
+            self.source.set_property("mute", true);
+            return match self.scrub_mode {
+                ScrubMode::Keyframe => self.seek_keyframe(position),
+                ScrubMode::Accurate => self.seek(position, true),
+            };
+        }
+
+        if scrub.playing {
+            scrub.pending = Some(position);
+            return Ok(());
+        }
+        scrub.playing = true;
+        self.seek_snippet(position, true)?;
+        self.source.set_state(gst::State::Playing)?;
+        Ok(())
+    }
+
+    /// Plays the next queued scrub snippet, or pauses until the user moves on.
+    pub(crate) fn scrub_segment_done(&mut self) -> Result<(), Error> {
+        let Some(scrub) = &mut self.scrub else {
+            return Ok(());
+        };
+        match scrub.pending.take() {
+            Some(position) => self.seek_snippet(position, false),
+            None => {
+                scrub.playing = false;
+                self.source.set_state(gst::State::Paused)?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn end_scrub(&mut self, position: Duration) -> Result<(), Error> {
+        let Some(scrub) = self.scrub.take() else {
+            return self.seek(position, true);
+        };
+        // a regular flushing seek also clears the stop position of the snippets
+        self.seek(position, true)?;
+        self.source.set_property("mute", scrub.was_muted);
+        self.set_paused(scrub.was_paused)
+    }
+
     pub(crate) fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
+        // keeping the direction, the rate can change without flushing the pipeline
+        if speed.signum() == self.speed.signum() && speed != 0.0 {
+            let mut flags = gst::SeekFlags::INSTANT_RATE_CHANGE;
+            if self.segment_looping && speed > 0.0 {
+                flags |= gst::SeekFlags::SEGMENT;
+            }
+            let instant = self.source.seek(
+                speed,
+                flags,
+                gst::SeekType::None,
+                gst::ClockTime::NONE,
+                gst::SeekType::None,
+                gst::ClockTime::NONE,
+            );
+            match instant {
+                Ok(()) => {
+                    self.speed = speed;
+                    return Ok(());
+                }
+                Err(err) => log::debug!("instant rate change unsupported, seeking instead: {err}"),
+            }
+        }
+
         let Some(position) = self.source.query_position::<gst::ClockTime>() else {
-            return Err(Error::Caps);
+            return Err(Error::Duration);
         };
         if speed > 0.0 {
+            let mut flags = gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE;
+            if self.segment_looping {
+                flags |= gst::SeekFlags::SEGMENT;
+            }
             self.source.seek(
                 speed,
-                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                flags,
                 gst::SeekType::Set,
                 position,
                 gst::SeekType::End,
@@ -217,102 +1080,585 @@ impl Internal {
         Ok(())
     }
 
-    pub(crate) fn restart_stream(&mut self) -> Result<(), Error> {
-        self.is_eos = false;
-        self.set_paused(false);
-        self.seek(0, false)?;
-        Ok(())
-    }
-
-    pub(crate) fn set_paused(&mut self, paused: bool) {
-        self.source
-            .set_state(if paused {
-                gst::State::Paused
-            } else {
-                gst::State::Playing
-            })
-            .unwrap(/* state was changed in ctor; state errors caught there */);
+    pub(crate) fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
 
-        // Set restart_stream flag to make the stream restart on the next Message::NextFrame
-        if self.is_eos && !paused {
-            self.restart_stream = true;
+        // live and non-seekable sources fall back to restarting once they ended
+        let mut query = gst::query::Seeking::new(gst::Format::Time);
+        let seekable = !self.is_live && self.source.query(&mut query) && query.result().0;
+        let segment_looping = looping && seekable;
+        if segment_looping == self.segment_looping {
+            return;
         }
-    }
+        self.segment_looping = segment_looping;
 
-    pub(crate) fn paused(&self) -> bool {
-        self.source.state(gst::ClockTime::ZERO).1 == gst::State::Paused
+        // the segment currently playing has to be replaced to end in `SEGMENT_DONE`;
+        // once disabled, the end of the segment is handled like the end of the stream
+        if segment_looping
+            && let Some(position) = self.source.query_position::<gst::ClockTime>()
+            && let Err(err) = self.seek(Duration::from_nanos(position.nseconds()), true)
+        {
+            log::warn!("cannot loop with segment seeks: {err}");
+            self.segment_looping = false;
+        }
     }
 
-    /// Syncs audio with video when there is (inevitably) latency presenting the frame.
-    pub(crate) fn set_av_offset(&mut self, offset: Duration) {
-        if self.sync_av {
-            self.sync_av_counter += 1;
-            self.sync_av_avg = self.sync_av_avg * (self.sync_av_counter - 1) / self.sync_av_counter
-                + offset.as_nanos() as u64 / self.sync_av_counter;
-            if self.sync_av_counter.is_multiple_of(128) {
-                self.source
-                    .set_property("av-offset", -(self.sync_av_avg as i64));
+    /// Counts down a loop once the media ended. Returns whether it loops.
+    pub(crate) fn take_loop(&mut self) -> bool {
+        if !self.looping || self.is_live {
+            return false;
+        }
+        match &mut self.loops_remaining {
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                true
             }
+            None => true,
         }
     }
 
-    fn toggle_hardware_volume(&mut self) {
-        let pipeline = &self.source;
-
-        let flags = pipeline.property_value("flags");
-        let flags_class =
-            FlagsClass::with_type(flags.type_()).expect("Playbin pipeline should have flags");
-
-        let builder = flags_class.builder_with_value(flags).unwrap();
-
-        let flags = if self.hard_volumne {
-            builder.set_by_nick("soft-volume")
-        } else {
-            builder.unset_by_nick("soft-volume")
+    /// Wraps a segment-seek loop around once its segment is done. Returns
+    /// whether the media looped, rather than ended because looping was disabled.
+    pub(crate) fn loop_segment(&mut self) -> Result<bool, Error> {
+        if !self.take_loop() {
+            return Ok(false);
         }
-        .build()
-        .unwrap();
-
-        pipeline.set_property_from_value("flags", &flags);
-        self.hard_volumne = !self.hard_volumne;
-    }
-
-    fn set_text(&mut self, text: TextTag) {
-        self.source.set_property("current-text", text.id);
-    }
-
-    fn set_audio(&mut self, audio: AudioTag) {
-        self.source.set_property("current-audio", audio.id);
+        // a non-flushing seek queues the start right behind the end, without a gap
+        self.source.seek(
+            self.speed,
+            gst::SeekFlags::SEGMENT | gst::SeekFlags::ACCURATE,
+            gst::SeekType::Set,
+            gst::ClockTime::ZERO,
+            gst::SeekType::Set,
+            gst::ClockTime::NONE,
+        )?;
+        *self.subtitle_cue.lock().map_err(|_| Error::Lock)? = None;
+        self.upload_text.store(true, Ordering::SeqCst);
+        Ok(true)
     }
 
-    fn set_text_offset(&mut self, offset: i64) {
-        self.source.set_property("text-offset", -offset);
-    }
-}
+    /// Handles the end of the stream, errors and restarts.
+    ///
+    /// Runs on the worker thread whenever it took `msg` off the bus, so that
+    /// videos loop and notice errors even while no [`VideoPlayer`](crate::VideoPlayer)
+    /// draws them. What happened is left in [`Internal::unreported`] for the widget to send.
+    pub(crate) fn handle_bus(&mut self, msg: &gst::Message) {
+        let mut restart_stream = std::mem::take(&mut self.restart_stream);
+        let mut eos_pause = false;
+        match msg.view() {
+            gst::MessageView::Error(err) => {
+                log::error!("bus returned an error: {err}");
+                let errors = &mut self.unreported.errors;
+                if errors.len() == MAX_UNREPORTED_ERRORS {
+                    errors.remove(0);
+                }
+                errors.push(PipelineError::from_message(err));
+            }
+            gst::MessageView::Eos(_) => {
+                self.unreported.ended = true;
+                if self.take_loop() {
+                    restart_stream = true;
+                } else {
+                    eos_pause = true;
+                }
+            }
+            gst::MessageView::SegmentDone(_) if self.scrub.is_some() => {
+                if let Err(err) = self.scrub_segment_done() {
+                    log::error!("cannot continue scrubbing: {err}");
+                }
+            }
+            gst::MessageView::SegmentDone(_) => {
+                // segment-seek loops end their segment rather than the stream
+                self.unreported.ended = true;
+                match self.loop_segment() {
+                    Ok(true) => self.unreported.restarted = true,
+                    Ok(false) => eos_pause = true,
+                    Err(err) => {
+                        log::error!("cannot loop segment: {err}");
+                        restart_stream = true;
+                    }
+                }
+            }
+            // posted by `Internal::wake_worker`
+            _ => {}
+        }
 
-/// A multimedia video loaded from a URI (e.g., a local file path or HTTP stream).
-#[derive(Debug)]
-pub struct Video(pub(crate) RwLock<Internal>);
+        // Don't run eos_pause if restart_stream is true; fixes "pausing" after restarting a stream
+        // live sources can be neither seeked back to the start nor prerolled
+        // when paused, so they only record that they ended
+        if restart_stream && !self.is_live {
+            self.unreported.restarted = true;
+            if let Err(err) = self.restart_stream() {
+                log::error!("cannot restart stream (can't seek): {err:#?}");
+            }
+        } else if eos_pause {
+            self.is_eos = true;
+            // ending a segment-seek loop posts no end of stream
+            self.events.send(Event::EndOfStream);
+            if !self.is_live
+                && let Err(err) = self.set_paused(true)
+            {
+                log::error!("cannot pause at the end of the stream: {err}");
+            }
+        }
+    }
 
-impl Drop for Video {
-    fn drop(&mut self) {
-        let inner = self.0.get_mut().expect("failed to lock");
+    pub(crate) fn restart_stream(&mut self) -> Result<(), Error> {
+        // present the cached first frame right away instead of the last one
+        // lingering until the seek completes
+        if let Some(first_frame) = self.first_frame.lock().ok().and_then(|frame| frame.clone())
+            && let Ok(mut frame) = self.frame.lock()
+        {
+            *frame = Frame(first_frame);
+            self.upload_frame.set();
+            self.frame_version.fetch_add(1, Ordering::SeqCst);
+        }
 
-        inner
-            .source
-            .set_state(gst::State::Null)
-            .expect("failed to set state");
+        self.is_eos = false;
+        self.set_paused(false)?;
+        self.seek(0, false)?;
+        Ok(())
+    }
 
-        inner.alive.store(false, Ordering::SeqCst);
-        if let Some(worker) = inner.worker.take()
-            && let Err(err) = worker.join()
+    pub(crate) fn set_paused(&mut self, paused: bool) -> Result<(), Error> {
+        // playback stays blocked until approved, see `Video::approve`
+        if !paused && self.awaiting_approval.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        // applied once resumed, see `Video::resume`
+        if let Some(suspended) = &mut self.suspended {
+            suspended.paused = paused;
+            return Ok(());
+        }
+
+        self.source.set_state(if paused {
+            gst::State::Paused
+        } else {
+            gst::State::Playing
+        })?;
+
+        // Set restart_stream flag to make the worker thread restart the stream
+        if self.is_eos && !paused {
+            self.restart_stream = true;
+            self.wake_worker();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn set_volume(&self, volume: f64) {
+        // an explicit volume takes over from any fade in progress
+        if let Ok(mut fade) = self.volume_fade.lock() {
+            *fade = None;
+        }
+        set_volume(&self.source, volume); // for some reason gstreamer unmutes when changing volume?
+    }
+
+    /// Wakes the worker thread, which otherwise waits for the end of the stream
+    /// or an error, see [`Internal::handle_bus`].
+    fn wake_worker(&self) {
+        let wake = gst::Structure::new_empty("iced-video-player-wake");
+        // fails while the pipeline is stopped, the worker polls then
+        if let Err(err) = self.bus.post(gst::message::Application::new(wake)) {
+            log::debug!("cannot wake worker thread: {err}");
+        }
+    }
+
+    pub(crate) fn paused(&self) -> bool {
+        self.source.state(gst::ClockTime::ZERO).1 == gst::State::Paused
+    }
+
+    /// Get the URI the media is loaded from, if any.
+    pub(crate) fn uri(&self) -> Option<String> {
+        self.source
+            .has_property("current-uri", None)
+            .then(|| self.source.property::<Option<String>>("current-uri"))
+            .flatten()
+    }
+
+    /// Get the size of the video as displayed, accounting for its pixel aspect
+    /// ratio and [`Orientation`].
+    pub(crate) fn display_size(&self) -> (i32, i32) {
+        let (width, height) = square_pixels(self.width, self.height, self.pixel_aspect_ratio);
+        if self.orientation.is_transposed() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Takes on the resolution of the latest frame. Returns whether it changed.
+    pub(crate) fn sync_size(&mut self) -> bool {
+        let Ok(size) = self.frame_size.lock().map(|size| *size) else {
+            return false;
+        };
+        if size == (self.width, self.height, self.pixel_aspect_ratio) {
+            return false;
+        }
+        (self.width, self.height, self.pixel_aspect_ratio) = size;
+        true
+    }
+
+    /// Get the latest frame as an image, converting it only once it changed.
+    pub(crate) fn frame_image(&self) -> Option<img::Handle> {
+        let version = self.frame_version.load(Ordering::SeqCst);
+        let mut cached = self.frame_image.lock().ok()?;
+        if let Some((cached_version, handle)) = cached.as_ref()
+            && *cached_version == version
         {
-            match err.downcast_ref::<String>() {
-                Some(e) => log::error!("Video thread panicked: {e}"),
-                None => log::error!("Video thread panicked with unknown reason"),
+            return Some(handle.clone());
+        }
+
+        let frame = self.frame.lock().ok()?;
+        let (width, height) = frame.size()?;
+        let handle = img::Handle::from_rgba(width, height, frame.rgba()?);
+        *cached = Some((version, handle.clone()));
+        Some(handle)
+    }
+
+    /// Get the timing information of the latest frame.
+    pub(crate) fn frame_info(&self) -> Option<FrameInfo> {
+        let frame = self.frame.lock().ok()?;
+        let pts = frame.0.buffer()?.pts();
+        Some(FrameInfo {
+            pts: pts.map(|pts| Duration::from_nanos(pts.nseconds())),
+            running_time: frame
+                .running_time()
+                .map(|running_time| Duration::from_nanos(running_time.nseconds())),
+            number: pts
+                .filter(|_| self.framerate.is_finite() && self.framerate > 0.0)
+                .map(|pts| (pts.seconds_f64() * self.framerate).round() as u64),
+        })
+    }
+
+    pub(crate) fn status(&self) -> PlaybackStatus {
+        if self.buffering {
+            return PlaybackStatus::Buffering;
+        }
+        if self.is_eos || self.paused() {
+            return PlaybackStatus::Paused;
+        }
+        if !self.has_video {
+            return PlaybackStatus::Playing;
+        }
+
+        // a frame is overdue once it's a couple of frame intervals late, but
+        // allow for some jitter in high framerate media
+        let threshold = Duration::from_secs_f64(2.0 / self.framerate).max(Duration::from_secs(1));
+        let stalled = self
+            .last_frame_time
+            .lock()
+            .is_ok_and(|time| time.elapsed() > threshold);
+        if stalled {
+            PlaybackStatus::Stalled
+        } else {
+            PlaybackStatus::Playing
+        }
+    }
+
+    /// Syncs audio with video when there is (inevitably) latency presenting the frame.
+    pub(crate) fn set_av_offset(&mut self, offset: Duration) {
+        if self.sync_av && !self.low_latency.load(Ordering::SeqCst) {
+            self.sync_av_counter += 1;
+            self.sync_av_avg = self.sync_av_avg * (self.sync_av_counter - 1) / self.sync_av_counter
+                + offset.as_nanos() as u64 / self.sync_av_counter;
+            if self.sync_av_counter.is_multiple_of(128) {
+                self.apply_av_offset();
             }
         }
     }
+
+    /// Combines the offset set by the user with the compensation for presentation latency.
+    fn apply_av_offset(&self) {
+        self.source
+            .set_property("av-offset", self.user_av_offset - self.sync_av_avg as i64);
+    }
+
+    /// Estimates the glass-to-glass latency of the current frame, i.e., how long
+    /// ago (in running time) it was captured.
+    pub(crate) fn update_latency_estimate(&mut self) {
+        let frame_time = self
+            .frame
+            .lock()
+            .ok()
+            .and_then(|frame| frame.running_time());
+        self.latency_estimate =
+            frame_time
+                .zip(self.source.current_running_time())
+                .map(|(frame_time, now)| {
+                    Duration::from_nanos(now.saturating_sub(frame_time).nseconds())
+                });
+    }
+
+    fn set_low_latency(&mut self, low_latency: bool) {
+        self.low_latency.store(low_latency, Ordering::SeqCst);
+        self.latency_estimate = None;
+
+        // present samples as soon as they arrive
+        self.video_sink.set_property("sync", !low_latency);
+
+        if low_latency && self.sync_av {
+            self.sync_av_avg = 0;
+            self.sync_av_counter = 0;
+            self.apply_av_offset();
+        }
+    }
+
+    pub(crate) fn set_late_frame_policy(&self, policy: LateFramePolicy) {
+        policy.store(&self.late_frame_policy);
+    }
+
+    /// Sets or unsets one of playbin's `flags` by its nick.
+    pub(crate) fn set_flag(&self, nick: &str, enabled: bool) {
+        set_flag(&self.source, nick, enabled);
+    }
+
+    /// Gets one of playbin's `flags` by its nick.
+    pub(crate) fn flag(&self, nick: &str) -> bool {
+        let flags = self.source.property_value("flags");
+        let flags_class =
+            FlagsClass::with_type(flags.type_()).expect("Playbin pipeline should have flags");
+
+        flags_class
+            .value_by_nick(nick)
+            .is_some_and(|value| flags_class.is_set(&flags, value.value()))
+    }
+
+    fn toggle_hardware_volume(&mut self) {
+        self.set_flag("soft-volume", self.hard_volumne);
+        self.hard_volumne = !self.hard_volumne;
+    }
+
+    fn set_text(&mut self, text: TextTag) {
+        self.select_track(gst::StreamType::TEXT, text.id);
+    }
+
+    fn set_audio(&mut self, audio: AudioTag) {
+        self.select_track(gst::StreamType::AUDIO, audio.id);
+    }
+
+    /// Get the streams of `stream_type` posted by `playbin3`, in order.
+    fn collection_streams(&self, stream_type: gst::StreamType) -> Vec<gst::Stream> {
+        self.streams
+            .iter()
+            .flat_map(|streams| streams.iter())
+            .filter(|stream| stream.stream_type().contains(stream_type))
+            .collect()
+    }
+
+    /// Get the number of audio or text tracks.
+    pub(crate) fn track_count(&self, stream_type: gst::StreamType) -> i32 {
+        if self.streams.is_some() {
+            return self.collection_streams(stream_type).len() as i32;
+        }
+        if stream_type == gst::StreamType::AUDIO {
+            self.source.property("n-audio")
+        } else {
+            self.source.property("n-text")
+        }
+    }
+
+    /// Get the index of the current audio or text track, or -1 if there is none.
+    pub(crate) fn current_track(&self, stream_type: gst::StreamType) -> i32 {
+        if self.streams.is_some() {
+            return self
+                .collection_streams(stream_type)
+                .iter()
+                .position(|stream| {
+                    stream.stream_id().is_some_and(|id| {
+                        self.selected_streams.iter().any(|selected| *selected == id)
+                    })
+                })
+                .map_or(-1, |index| index as i32);
+        }
+        if stream_type == gst::StreamType::AUDIO {
+            self.source.property("current-audio")
+        } else {
+            self.source.property("current-text")
+        }
+    }
+
+    /// Get the tags of an audio or text track.
+    fn track_tags(&self, stream_type: gst::StreamType, id: i32) -> Option<gst::TagList> {
+        if self.streams.is_some() {
+            return usize::try_from(id)
+                .ok()
+                .and_then(|id| self.collection_streams(stream_type).into_iter().nth(id))?
+                .tags();
+        }
+        let signal = if stream_type == gst::StreamType::AUDIO {
+            "get-audio-tags"
+        } else {
+            "get-text-tags"
+        };
+        self.source
+            .emit_by_name::<Option<gst::TagList>>(signal, &[&id])
+    }
+
+    /// Switches to an audio or text track.
+    fn select_track(&mut self, stream_type: gst::StreamType, id: i32) {
+        if self.streams.is_none() {
+            let property = if stream_type == gst::StreamType::AUDIO {
+                "current-audio"
+            } else {
+                "current-text"
+            };
+            self.source.set_property(property, id);
+            return;
+        }
+
+        let Some(stream_id) = usize::try_from(id)
+            .ok()
+            .and_then(|id| self.collection_streams(stream_type).into_iter().nth(id))
+            .and_then(|stream| stream.stream_id())
+        else {
+            return;
+        };
+        // keep the selection of the other stream types
+        let replaced: Vec<_> = self
+            .collection_streams(stream_type)
+            .iter()
+            .filter_map(|stream| stream.stream_id())
+            .map(|id| id.to_string())
+            .collect();
+        let mut selected: Vec<_> = self
+            .selected_streams
+            .iter()
+            .filter(|id| !replaced.contains(id))
+            .cloned()
+            .collect();
+        selected.push(stream_id.to_string());
+
+        self.source.send_event(gst::event::SelectStreams::new(
+            selected.iter().map(String::as_str),
+        ));
+        self.selected_streams = selected;
+    }
+
+    /// Get an audio track by its index.
+    pub(crate) fn audio_track(&self, id: i32) -> Option<AudioTag> {
+        if !(0..self.track_count(gst::StreamType::AUDIO)).contains(&id) {
+            return None;
+        }
+        let tags = self.track_tags(gst::StreamType::AUDIO, id);
+        let tag = |get: fn(&gst::TagListRef) -> Option<String>| tags.as_deref().and_then(get);
+        let caps = self.audio_caps(id);
+        let field = |name: &str| {
+            caps.as_ref()
+                .and_then(|caps| caps.structure(0)?.get::<i32>(name).ok())
+                .and_then(|value| u32::try_from(value).ok())
+        };
+
+        Some(AudioTag {
+            id,
+            language_code: tag(|tags| {
+                Some(tags.get::<gst::tags::LanguageCode>()?.get().to_owned())
+            }),
+            codec: tag(|tags| Some(tags.get::<gst::tags::AudioCodec>()?.get().to_owned())),
+            title: tag(|tags| Some(tags.get::<gst::tags::Title>()?.get().to_owned())),
+            channels: field("channels"),
+            sample_rate: field("rate"),
+            bitrate: tags.as_deref().and_then(|tags| {
+                tags.get::<gst::tags::Bitrate>()
+                    .or_else(|| tags.get::<gst::tags::NominalBitrate>())
+                    .map(|bitrate| bitrate.get())
+            }),
+        })
+    }
+
+    /// Get the caps of an audio track, once it is decoded.
+    fn audio_caps(&self, id: i32) -> Option<gst::Caps> {
+        if self.streams.is_some() {
+            return usize::try_from(id)
+                .ok()
+                .and_then(|id| {
+                    self.collection_streams(gst::StreamType::AUDIO)
+                        .into_iter()
+                        .nth(id)
+                })?
+                .caps();
+        }
+        self.source
+            .emit_by_name::<Option<gst::Pad>>("get-audio-pad", &[&id])?
+            .current_caps()
+    }
+
+    /// Get a subtitle track by its index. Tracks are listed even without tags.
+    pub(crate) fn text_track(&self, id: i32) -> Option<TextTag> {
+        if !(0..self.track_count(gst::StreamType::TEXT)).contains(&id) {
+            return None;
+        }
+        let tags = self.track_tags(gst::StreamType::TEXT, id);
+        let tag = |get: fn(&gst::TagListRef) -> Option<String>| tags.as_deref().and_then(get);
+
+        Some(TextTag {
+            id,
+            language_code: tag(|tags| {
+                Some(tags.get::<gst::tags::LanguageCode>()?.get().to_owned())
+            }),
+            title: tag(|tags| Some(tags.get::<gst::tags::Title>()?.get().to_owned())),
+            codec: tag(|tags| Some(tags.get::<gst::tags::SubtitleCodec>()?.get().to_owned())),
+        })
+    }
+
+    /// Selects the audio and subtitle tracks best matching the preferred
+    /// languages, leaving the current selection alone if nothing matches.
+    pub(crate) fn select_preferred_tracks(&mut self) {
+        if self.preferred_languages.is_empty() {
+            return;
+        }
+
+        let rank = |language: &str| {
+            self.preferred_languages
+                .iter()
+                .position(|preferred| language_matches(language, preferred))
+        };
+
+        let n = self.track_count(gst::StreamType::AUDIO);
+        let audio = (0..n)
+            .filter_map(|id| self.audio_track(id))
+            .filter_map(|audio| Some((rank(audio.language_code.as_deref()?)?, audio)))
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, audio)| audio);
+
+        let n = self.track_count(gst::StreamType::TEXT);
+        let text = (0..n)
+            .filter_map(|id| self.text_track(id))
+            .filter_map(|text| {
+                let sdh_mismatch =
+                    is_sdh(text.title.as_deref().unwrap_or_default()) != self.prefer_sdh;
+                Some(((rank(text.language_code.as_deref()?)?, sdh_mismatch), text))
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, text)| text);
+
+        if let Some(audio) = audio {
+            self.set_audio(audio);
+        }
+        if let Some(text) = text {
+            self.set_text(text);
+        }
+    }
+
+    fn set_text_offset(&mut self, offset: i64) {
+        self.source.set_property("text-offset", -offset);
+    }
+}
+
+/// A multimedia video loaded from a URI (e.g., a local file path or HTTP stream).
+#[derive(Debug)]
+pub struct Video(pub(crate) Arc<RwLock<Internal>>);
+
+impl Drop for Video {
+    fn drop(&mut self) {
+        let Ok(mut inner) = self.0.write() else {
+            log::error!("failed to lock video for shutdown");
+            return;
+        };
+        if let Err(err) = inner.shutdown() {
+            log::error!("failed to shut down video: {err}");
+        }
+    }
 }
 
 impl Video {
@@ -320,54 +1666,33 @@ impl Video {
     /// Both balance and gamma filters are enabled and set to their default
     /// values.
     ///
-    /// Note that live sources will report the duration to be zero.
+    /// Note that live sources will report the duration to be zero, see
+    /// [`Video::is_live`].
+    ///
+    /// Use [`Video::builder`] to configure the video before playback starts.
     pub fn new(uri: &url::Url) -> Result<Self, Error> {
-        gst::init()?;
-
-        let pipeline = format!(
-            "playbin uri=\"{}\" text-sink=\"appsink name=iced_text sync=true drop=true\" video-sink=\"videoscale ! videoconvert ! appsink name=iced_video drop=true caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1\" video-filter=\"videobalance name=balance ! gamma name=gamma\" audio-filter= \"pitch name=pitch\"",
-            uri.as_str()
-        );
-        let pipeline = gst::parse::launch(pipeline.as_ref())?
-            .downcast::<gst::Pipeline>()
-            .map_err(|_| Error::Cast)?;
-
-        let video_sink: gst::Element = pipeline.property("video-sink");
-        let pad = video_sink.pads().first().cloned().unwrap();
-        let pad = pad.dynamic_cast::<gst::GhostPad>().unwrap();
-        let bin = pad
-            .parent_element()
-            .unwrap()
-            .downcast::<gst::Bin>()
-            .unwrap();
-        let video_sink = bin.by_name("iced_video").unwrap();
-        let video_sink = video_sink.downcast::<gst_app::AppSink>().unwrap();
-
-        let text_sink: gst::Element = pipeline.property("text-sink");
-        let text_sink = text_sink.downcast::<gst_app::AppSink>().unwrap();
-
-        let filter: gst::Element = pipeline.property("video-filter");
-        let pad = filter.pads().first().cloned().unwrap();
-        let pad = pad.dynamic_cast::<gst::GhostPad>().unwrap();
-        let bin = pad
-            .parent_element()
-            .unwrap()
-            .downcast::<gst::Bin>()
-            .unwrap();
-        let balance = bin.by_name("balance").unwrap();
-
-        let gamma: gst::Element = bin.by_name("gamma").unwrap();
-
-        let filters = VideoFilters::all(balance, gamma);
+        Self::builder(uri).build()
+    }
 
-        let mut output = Self::from_gst_pipeline(pipeline, video_sink, Some(text_sink))?;
-        output.set_video_filters(filters);
+    /// Like [`Video::new`], but the video is created on a separate thread so
+    /// that the caller is not blocked while the pipeline prerolls.
+    ///
+    /// The returned future can be run with `iced::Task::perform`, e.g., to show
+    /// a loading state in the meantime.
+    pub fn new_async(
+        uri: &url::Url,
+    ) -> impl std::future::Future<Output = Result<Self, Error>> + Send + 'static {
+        Self::builder(uri).build_async()
+    }
 
-        Ok(output)
+    /// Creates a [`VideoBuilder`] for a video which loads from `uri`, allowing
+    /// it to be configured before playback starts.
+    pub fn builder(uri: &url::Url) -> VideoBuilder {
+        VideoBuilder::new(uri)
     }
 
     /// Creates a new video based on an existing GStreamer pipeline and appsink.
-    /// Expects an `appsink` plugin with `caps=video/x-raw,format=NV12`.
+    /// Expects an `appsink` plugin with `caps=video/x-raw,format=NV12` (or `format=RGBA`).
     ///
     /// An optional `text_sink` can be provided, which enables subtitle messages
     /// to be emitted.
@@ -378,6 +1703,108 @@ impl Video {
         pipeline: gst::Pipeline,
         video_sink: gst_app::AppSink,
         text_sink: Option<gst_app::AppSink>,
+    ) -> Result<Self, Error> {
+        Self::from_gst_pipeline_with(pipeline, video_sink, text_sink, false, None)
+    }
+
+    /// Like [`Video::from_gst_pipeline`], but the pipeline only prerolls in
+    /// `PAUSED` rather than starting to play, so no audio is heard before the
+    /// caller unpauses it. The first frame is still shown.
+    pub fn from_gst_pipeline_paused(
+        pipeline: gst::Pipeline,
+        video_sink: gst_app::AppSink,
+        text_sink: Option<gst_app::AppSink>,
+    ) -> Result<Self, Error> {
+        Self::from_gst_pipeline_with(pipeline, video_sink, text_sink, true, None)
+    }
+
+    /// Creates a new video from frames generated by the application (e.g., a
+    /// game capture or a computer vision pipeline), which are displayed at `framerate`.
+    ///
+    /// Sending blocks while frames are queued ahead of playback. The first frame
+    /// must be sent within a few seconds, as this waits for it to preroll. The video
+    /// reaches its end once the sender is dropped.
+    pub fn from_frames(
+        width: u32,
+        height: u32,
+        framerate: f64,
+        format: FrameFormat,
+        frames: std::sync::mpsc::Receiver<Vec<u8>>,
+    ) -> Result<Self, Error> {
+        gst::init()?;
+
+        if !framerate.is_finite() || framerate <= 0.0 {
+            return Err(Error::Framerate(framerate));
+        }
+        let fraction =
+            gst::Fraction::approximate_f64(framerate).ok_or(Error::Framerate(framerate))?;
+        let format = format.caps_name();
+
+        let pipeline = gst::parse::launch(&format!(
+            "appsrc name=iced_src format=time block=true caps=video/x-raw,format={format},width={width},height={height},framerate={}/{} ! videoconvert ! appsink name=iced_video drop=true caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1",
+            fraction.numer(),
+            fraction.denom()
+        ))?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| Error::Cast("gst::Pipeline"))?;
+        let src = pipeline
+            .by_name("iced_src")
+            .and_then(|src| src.downcast::<gst_app::AppSrc>().ok())
+            .ok_or(Error::Cast("gst_app::AppSrc"))?;
+        let video_sink = pipeline
+            .by_name("iced_video")
+            .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+            .ok_or_else(|| Error::AppSink("iced_video".into()))?;
+
+        let frame_duration = Duration::from_secs_f64(1.0 / framerate);
+        std::thread::spawn(move || {
+            for (n, data) in frames.iter().enumerate() {
+                let mut buffer = gst::Buffer::from_mut_slice(data);
+                if let Some(buffer) = buffer.get_mut() {
+                    let pts = frame_duration.as_nanos() as u64 * n as u64;
+                    buffer.set_pts(gst::ClockTime::from_nseconds(pts));
+                    buffer.set_duration(gst::ClockTime::from_nseconds(
+                        frame_duration.as_nanos() as _
+                    ));
+                }
+                // fails once the pipeline is shut down
+                if src.push_buffer(buffer).is_err() {
+                    return;
+                }
+            }
+            let _ = src.end_of_stream();
+        });
+
+        Self::from_gst_pipeline(pipeline, video_sink, None)
+    }
+
+    /// Creates a new video showing the live feed of a capture device (e.g., a
+    /// webcam), see [`CaptureDevice::list`].
+    pub fn from_capture_device(device: &CaptureDevice) -> Result<Self, Error> {
+        gst::init()?;
+        let (pipeline, video_sink) = capture::pipeline(device.device.create_element(None)?)?;
+        Self::from_gst_pipeline(pipeline, video_sink, None)
+    }
+
+    /// Creates a new video showing the live contents of the `monitor`th display,
+    /// e.g., to preview a screen share.
+    ///
+    /// **Note:** On Linux, this captures the whole X11 screen regardless of
+    /// `monitor`. Wayland sessions are not supported.
+    pub fn from_screen(monitor: u32) -> Result<Self, Error> {
+        gst::init()?;
+        let (pipeline, video_sink) = capture::pipeline(capture::screen_source(monitor)?)?;
+        Self::from_gst_pipeline(pipeline, video_sink, None)
+    }
+
+    /// Like [`Video::from_gst_pipeline`], but allows the video to start paused
+    /// and/or at a given position.
+    pub(crate) fn from_gst_pipeline_with(
+        pipeline: gst::Pipeline,
+        video_sink: gst_app::AppSink,
+        text_sink: Option<gst_app::AppSink>,
+        paused: bool,
+        start_at: Option<Position>,
     ) -> Result<Self, Error> {
         gst::init()?;
         static NEXT_ID: AtomicU64 = AtomicU64::new(0);
@@ -385,30 +1812,131 @@ impl Video {
 
         // We need to ensure we stop the pipeline if we hit an error,
         // or else there may be audio left playing in the background.
+        // The error posted by the failing element is preferred, as it
+        // carries far more detail than e.g. a failed state change.
         macro_rules! cleanup {
             ($expr:expr) => {
                 $expr.map_err(|e| {
+                    let bus = pipeline.bus();
+                    // a missing decoder is what usually makes playback fail
+                    let missing = bus
+                        .as_ref()
+                        .map(MissingPlugin::from_bus)
+                        .unwrap_or_default();
+                    let details = bus.and_then(|bus| PipelineError::from_bus(&bus));
                     let _ = pipeline.set_state(gst::State::Null);
-                    e
+                    if !missing.is_empty() {
+                        return Error::MissingPlugin(missing);
+                    }
+                    details.map_or_else(|| Error::from(e), Error::Pipeline)
                 })
             };
         }
 
         let pad = video_sink.pads().first().cloned().unwrap();
 
-        cleanup!(pipeline.set_state(gst::State::Playing))?;
+        // prerolling in PAUSED lets the initial seek happen before anything is played
+        let preroll_state = if paused || start_at.is_some() {
+            gst::State::Paused
+        } else {
+            gst::State::Playing
+        };
+        cleanup!(pipeline.set_state(preroll_state))?;
 
         // wait for up to 5 seconds until the decoder gets the source capabilities
         cleanup!(pipeline.state(gst::ClockTime::from_seconds(5)).0)?;
 
+        if let Some(position) = start_at {
+            cleanup!(pipeline.seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::GenericFormattedValue::from(position),
+            ))?;
+            cleanup!(pipeline.state(gst::ClockTime::from_seconds(5)).0)?;
+        }
+
+        if preroll_state != gst::State::Playing && !paused {
+            cleanup!(pipeline.set_state(gst::State::Playing))?;
+        }
+
+        // `playbin3` posts its streams while prerolling, rather than counting them
+        let bus = pipeline.bus();
+        let streams = bus
+            .as_ref()
+            .and_then(|bus| bus.pop_filtered(&[gst::MessageType::StreamCollection]))
+            .and_then(|msg| match msg.view() {
+                gst::MessageView::StreamCollection(collection) => {
+                    Some(collection.stream_collection())
+                }
+                _ => None,
+            });
+        let selected_streams = bus
+            .as_ref()
+            .and_then(|bus| bus.pop_filtered(&[gst::MessageType::StreamsSelected]))
+            .and_then(|msg| match msg.view() {
+                gst::MessageView::StreamsSelected(selected) => Some(stream_ids(selected)),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        // audio-only media never negotiates caps on the video sink, unless
+        // playbin renders a visualization into it
+        let has_video = pad.current_caps().is_some()
+            || match &streams {
+                Some(streams) => streams
+                    .iter()
+                    .any(|stream| stream.stream_type().contains(gst::StreamType::VIDEO)),
+                None => {
+                    !pipeline.has_property("n-video", None)
+                        || pipeline.property::<i32>("n-video") > 0
+                }
+            };
+
         // extract resolution and framerate
         // TODO(jazzfool): maybe we want to extract some other information too?
-        let caps = cleanup!(pad.current_caps().ok_or(Error::Caps))?;
-        let s = cleanup!(caps.structure(0).ok_or(Error::Caps))?;
-        let width = cleanup!(s.get::<i32>("width").map_err(|_| Error::Caps))?;
-        let height = cleanup!(s.get::<i32>("height").map_err(|_| Error::Caps))?;
-        let framerate = cleanup!(s.get::<gst::Fraction>("framerate").map_err(|_| Error::Caps))?;
-        let framerate = framerate.numer() as f64 / framerate.denom() as f64;
+        let (width, height, pixel_aspect_ratio, framerate) = if has_video {
+            let caps = cleanup!(pad.current_caps().ok_or_else(|| Error::Caps(format!(
+                "no caps negotiated on {}",
+                pad.path_string()
+            ))))?;
+            let s = cleanup!(
+                caps.structure(0)
+                    .ok_or_else(|| Error::Caps(format!("empty caps: {caps}")))
+            )?;
+            let field =
+                |name: &str| Error::Caps(format!("missing or invalid `{name}` in caps: {caps}"));
+            let width = cleanup!(s.get::<i32>("width").map_err(|_| field("width")))?;
+            let height = cleanup!(s.get::<i32>("height").map_err(|_| field("height")))?;
+            let framerate = cleanup!(
+                s.get::<gst::Fraction>("framerate")
+                    .map_err(|_| field("framerate"))
+            )?;
+            let framerate = framerate.numer() as f64 / framerate.denom() as f64;
+            // anamorphic media (e.g., DVDs) is scaled to its display aspect when drawn
+            let pixel_aspect_ratio = s
+                .get::<gst::Fraction>("pixel-aspect-ratio")
+                .unwrap_or(gst::Fraction::new(1, 1));
+            (width, height, pixel_aspect_ratio, framerate)
+        } else {
+            (0, 0, gst::Fraction::new(1, 1), AUDIO_ONLY_FRAMERATE)
+        };
+
+        // audio-only media shows its attached picture (e.g., album art) instead, if any
+        let cover_art = if has_video {
+            None
+        } else {
+            attached_picture(&pipeline).and_then(|picture| {
+                decode_picture(&picture)
+                    .inspect_err(|err| log::warn!("failed to decode cover art: {err}"))
+                    .ok()
+            })
+        };
+        let (width, height) = cover_art
+            .as_ref()
+            .and_then(|art| art.caps())
+            .and_then(|caps| VideoInfo::from_caps(caps).ok())
+            .map_or((width, height), |info| {
+                (info.width() as i32, info.height() as i32)
+            });
 
         if framerate.is_nan()
             || framerate.is_infinite()
@@ -428,123 +1956,407 @@ impl Video {
 
         let sync_av = pipeline.has_property("av-offset", None);
 
+        // live sources (e.g., RTSP, webcams) report themselves through the latency query
+        let is_live = {
+            let mut query = gst::query::Latency::new();
+            pipeline.query(&mut query) && query.result().0
+        };
+
+        let orientation = if pipeline.has_property("current-video", None) {
+            let id = pipeline.property::<i32>("current-video").max(0);
+            pipeline
+                .emit_by_name::<Option<gst::TagList>>("get-video-tags", &[&id])
+                .and_then(|tags| Orientation::from_tags(&tags))
+                .unwrap_or_default()
+        } else {
+            Orientation::default()
+        };
+
+        // containers post their table of contents while prerolling
+        let chapters = pipeline
+            .bus()
+            .and_then(|bus| bus.pop_filtered(&[gst::MessageType::Toc]))
+            .and_then(|msg| match msg.view() {
+                gst::MessageView::Toc(toc) => Some(toc_chapters(&toc.toc().0)),
+                _ => None,
+            })
+            .unwrap_or_default();
+
         // NV12 = 12bpp
-        let frame = Arc::new(Mutex::new(Frame::empty()));
-        let upload_frame = Arc::new(AtomicBool::new(false));
+        let frame = Arc::new(Mutex::new(
+            cover_art.clone().map_or_else(Frame::empty, Frame),
+        ));
+        let upload_frame = Arc::new(PendingFrame::new(cover_art.is_some()));
+        let frame_version = Arc::new(AtomicU64::new(cover_art.is_some() as u64));
         let alive = Arc::new(AtomicBool::new(true));
         let last_frame_time = Arc::new(Mutex::new(Instant::now()));
 
         let frame_ref = Arc::clone(&frame);
+        let first_frame = Arc::new(Mutex::new(None));
+        let first_frame_ref = Arc::clone(&first_frame);
         let upload_frame_ref = Arc::clone(&upload_frame);
+        let frame_version_ref = Arc::clone(&frame_version);
+        let frame_size = Arc::new(Mutex::new((width, height, pixel_aspect_ratio)));
+        let frame_size_ref = Arc::clone(&frame_size);
         let alive_ref = Arc::clone(&alive);
+        let resident = Arc::new(AtomicBool::new(true));
+        let resident_ref = Arc::clone(&resident);
+        let volume_fade = Arc::new(Mutex::new(None::<VolumeFade>));
         let last_frame_time_ref = Arc::clone(&last_frame_time);
 
-        let subtitle_text = Arc::new(Mutex::new(None));
+        let subtitle_cue = Arc::new(Mutex::new(None));
         let upload_text = Arc::new(AtomicBool::new(false));
-        let subtitle_text_ref = Arc::clone(&subtitle_text);
+        let subtitle_cue_ref = Arc::clone(&subtitle_cue);
         let upload_text_ref = Arc::clone(&upload_text);
+        let subtitle_is_ass = Arc::new(AtomicBool::new(false));
+        let subtitle_is_ass_ref = Arc::clone(&subtitle_is_ass);
+
+        let low_latency = Arc::new(AtomicBool::new(false));
+
+        let late_frame_policy = Arc::new(AtomicU8::new(0));
+        let late_frame_policy_ref = Arc::clone(&late_frame_policy);
+
+        let frame_processor = Arc::new(Mutex::new(None::<FrameProcessor>));
+        let frame_processor_ref = Arc::clone(&frame_processor);
+
+        let recorder = Arc::new(Mutex::new(None::<Recorder>));
+        let recorder_ref = Arc::clone(&recorder);
+
+        let scene_detector = Arc::new(Mutex::new(None::<SceneDetector>));
+        let scene_detector_ref = Arc::clone(&scene_detector);
+
+        let gates = Arc::new(Mutex::new(Vec::<Duration>::new()));
+        let awaiting_approval = Arc::new(AtomicBool::new(false));
+        let gates_ref = Arc::clone(&gates);
+        let awaiting_approval_ref = Arc::clone(&awaiting_approval);
+
+        let events = Subscribers::default();
+        if let Some(bus) = pipeline.bus() {
+            events.forward(&bus);
+        }
 
         let pipeline_ref = pipeline.clone();
+        let sink_pad = video_sink.static_pad("sink");
+        // flushing (e.g., for a seek) waits for the streaming thread, which may
+        // be waiting for the UI, see `LateFramePolicy::Block`
+        if let Some(pad) = &sink_pad {
+            let upload_frame = Arc::clone(&upload_frame);
+            pad.add_probe(gst::PadProbeType::EVENT_FLUSH, move |_, _| {
+                upload_frame.notify();
+                gst::PadProbeReturn::Ok
+            });
+        }
+        let mut clear_subtitles_at = None;
+        let mut last_caps = None::<gst::Caps>;
+
+        // runs on the streaming thread of the sink as each frame arrives
+        let deliver = move |sample: gst::Sample| -> Result<(), gst::FlowError> {
+            // a suspended pipeline produces no frames until resumed
+            if !resident_ref.load(Ordering::Acquire) {
+                return Ok(());
+            }
 
-        let worker = std::thread::spawn(move || {
-            let mut clear_subtitles_at = None;
-
-            while alive_ref.load(Ordering::Acquire) {
-                if let Err(gst::FlowError::Error) = (|| -> Result<(), gst::FlowError> {
-                    let sample =
-                        if pipeline_ref.state(gst::ClockTime::ZERO).1 != gst::State::Playing {
-                            video_sink
-                                .try_pull_preroll(gst::ClockTime::from_mseconds(16))
-                                .ok_or(gst::FlowError::Eos)?
-                        } else {
-                            video_sink
-                                .try_pull_sample(gst::ClockTime::from_mseconds(16))
-                                .ok_or(gst::FlowError::Eos)?
-                        };
-
-                    *last_frame_time_ref
-                        .lock()
-                        .map_err(|_| gst::FlowError::Error)? = Instant::now();
-
-                    let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
-                    let frame_pts = buffer.pts().ok_or(gst::FlowError::Error)?;
-                    {
-                        let mut frame_guard =
-                            frame_ref.lock().map_err(|_| gst::FlowError::Error)?;
-                        *frame_guard = Frame(sample);
-                    }
+            *last_frame_time_ref
+                .lock()
+                .map_err(|_| gst::FlowError::Error)? = Instant::now();
+
+            let sample = match frame_processor_ref
+                .lock()
+                .map_err(|_| gst::FlowError::Error)?
+                .as_mut()
+            {
+                Some(processor) => processor.process(sample),
+                None => sample,
+            };
 
-                    upload_frame_ref.swap(true, Ordering::SeqCst);
+            if let Some(recorder) = recorder_ref
+                .lock()
+                .map_err(|_| gst::FlowError::Error)?
+                .as_mut()
+            {
+                recorder.push(&sample);
+            }
 
-                    if let Some(at) = clear_subtitles_at
-                        && frame_pts >= at
-                    {
-                        *subtitle_text_ref
-                            .lock()
-                            .map_err(|_| gst::FlowError::Error)? = None;
-                        upload_text_ref.store(true, Ordering::SeqCst);
-                        clear_subtitles_at = None;
+            if let Some(detector) = scene_detector_ref
+                .lock()
+                .map_err(|_| gst::FlowError::Error)?
+                .as_mut()
+            {
+                detector.push(&sample);
+            }
+
+            let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
+            let frame_pts = buffer.pts().ok_or(gst::FlowError::Error)?;
+
+            // keep a copy of the first frame, so restarts can present it instantly;
+            // the decoder's buffer is not held onto, as its pool may be small
+            {
+                let mut first_frame = first_frame_ref.lock().map_err(|_| gst::FlowError::Error)?;
+                if first_frame.is_none()
+                    && frame_pts.nseconds() < frame_interval(&sample).as_nanos() as u64
+                    && let Ok(copy) = buffer.copy_deep()
+                {
+                    let mut builder = gst::Sample::builder().buffer(&copy);
+                    if let Some(caps) = sample.caps() {
+                        builder = builder.caps(caps);
                     }
+                    *first_frame = Some(builder.build());
+                }
+            }
 
-                    let text = text_sink
-                        .as_ref()
-                        .and_then(|sink| sink.try_pull_sample(gst::ClockTime::from_seconds(0)));
-
-                    if let Some(text) = text {
-                        let text = text.buffer().ok_or(gst::FlowError::Error)?;
-                        let text_duration = text.duration().ok_or(gst::FlowError::Error)?;
-
-                        let map = text.map_readable().map_err(|_| gst::FlowError::Error)?;
-                        let text = std::str::from_utf8(map.as_slice())
-                            .map_err(|_| gst::FlowError::Error)?
-                            .to_string();
-                        *subtitle_text_ref
-                            .lock()
-                            .map_err(|_| gst::FlowError::Error)? = Some(text);
-                        upload_text_ref.store(true, Ordering::SeqCst);
-                        // should be text_pts + text_duration
-                        // but playbin can specify text-offset which does not update the text buffer pts
-                        // so we'll just take it as starting on this frame
-                        clear_subtitles_at = Some(frame_pts + text_duration);
+            let gate_reached = {
+                let mut gates = gates_ref.lock().map_err(|_| gst::FlowError::Error)?;
+                let count = gates.len();
+                gates.retain(|gate| frame_pts.nseconds() < gate.as_nanos() as u64);
+                gates.len() != count
+            };
+            if gate_reached {
+                awaiting_approval_ref.store(true, Ordering::SeqCst);
+                // the state can't be changed from the streaming thread itself
+                pipeline_ref.call_async(|pipeline| {
+                    let _ = pipeline.set_state(gst::State::Paused);
+                });
+            }
+            match LateFramePolicy::load(&late_frame_policy_ref) {
+                LateFramePolicy::DropOldest => {}
+                LateFramePolicy::DropNewest => {
+                    if upload_frame_ref.is_pending() {
+                        return Ok(());
                     }
+                }
+                LateFramePolicy::Block => {
+                    // flushing waits for the streaming thread, so it must not
+                    // wait for the UI in turn
+                    upload_frame_ref.wait(|| {
+                        !alive_ref.load(Ordering::Acquire)
+                            || sink_pad.as_ref().is_some_and(|pad| {
+                                pad.pad_flags().contains(gst::PadFlags::FLUSHING)
+                            })
+                    });
+                }
+            }
 
-                    Ok(())
-                })() {
-                    log::error!("error pulling frame");
+            // the source may renegotiate its resolution mid-stream (e.g., adaptive streams)
+            if sample.caps() != last_caps.as_deref() {
+                last_caps = sample.caps_owned();
+                if let Some(info) = sample
+                    .caps()
+                    .and_then(|caps| VideoInfo::from_caps(caps).ok())
+                {
+                    *frame_size_ref.lock().map_err(|_| gst::FlowError::Error)? =
+                        (info.width() as i32, info.height() as i32, info.par());
                 }
             }
-        });
 
-        Ok(Video(RwLock::new(Internal {
+            {
+                let mut frame_guard = frame_ref.lock().map_err(|_| gst::FlowError::Error)?;
+                *frame_guard = Frame(sample);
+            }
+
+            upload_frame_ref.set();
+            frame_version_ref.fetch_add(1, Ordering::SeqCst);
+
+            if let Some(at) = clear_subtitles_at
+                && frame_pts >= at
+            {
+                *subtitle_cue_ref.lock().map_err(|_| gst::FlowError::Error)? = None;
+                upload_text_ref.store(true, Ordering::SeqCst);
+                clear_subtitles_at = None;
+            }
+
+            let text = text_sink
+                .as_ref()
+                .and_then(|sink| sink.try_pull_sample(gst::ClockTime::from_seconds(0)));
+
+            if let Some(text) = text {
+                let is_ass = text
+                    .caps()
+                    .and_then(|caps| caps.structure(0))
+                    .is_some_and(|s| {
+                        s.name() == "application/x-ass" || s.name() == "application/x-ssa"
+                    });
+                subtitle_is_ass_ref.store(is_ass, Ordering::SeqCst);
+
+                let text = text.buffer().ok_or(gst::FlowError::Error)?;
+                let text_duration = text.duration().ok_or(gst::FlowError::Error)?;
+                let text_pts = text.pts().unwrap_or(frame_pts);
+
+                let map = text.map_readable().map_err(|_| gst::FlowError::Error)?;
+                let text = std::str::from_utf8(map.as_slice())
+                    .map_err(|_| gst::FlowError::Error)?
+                    .to_string();
+                *subtitle_cue_ref.lock().map_err(|_| gst::FlowError::Error)? = Some(SubtitleCue {
+                    text,
+                    start: Duration::from_nanos(text_pts.nseconds()),
+                    duration: Duration::from_nanos(text_duration.nseconds()),
+                });
+                upload_text_ref.store(true, Ordering::SeqCst);
+                // should be text_pts + text_duration
+                // but playbin can specify text-offset which does not update the text buffer pts
+                // so we'll just take it as starting on this frame
+                clear_subtitles_at = Some(frame_pts + text_duration);
+            }
+
+            Ok(())
+        };
+        let deliver = Arc::new(Mutex::new(deliver));
+        let deliver_preroll = Arc::clone(&deliver);
+        let deliver_queued = Arc::clone(&deliver);
+        let pipeline_preroll = pipeline.clone();
+        video_sink.set_callbacks(
+            gst_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                    let mut deliver = deliver.lock().map_err(|_| gst::FlowError::Error)?;
+                    if (*deliver)(sample).is_err() {
+                        log::error!("error handling frame");
+                    }
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .new_preroll(move |sink| {
+                    let sample = sink.pull_preroll().map_err(|_| gst::FlowError::Eos)?;
+                    // while playing, the prerolled frame is delivered again as a sample
+                    if pipeline_preroll.current_state() != gst::State::Playing {
+                        let mut deliver =
+                            deliver_preroll.lock().map_err(|_| gst::FlowError::Error)?;
+                        if (*deliver)(sample).is_err() {
+                            log::error!("error handling frame");
+                        }
+                    }
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+
+        // frames which arrived before the callbacks were set are delivered right away
+        let queued = if pipeline.current_state() == gst::State::Playing {
+            std::iter::from_fn(|| video_sink.try_pull_sample(gst::ClockTime::ZERO)).last()
+        } else {
+            video_sink.try_pull_preroll(gst::ClockTime::ZERO)
+        };
+        if let Some(sample) = queued
+            && let Ok(mut deliver) = deliver_queued.lock()
+            && (*deliver)(sample).is_err()
+        {
+            log::error!("error handling frame");
+        }
+
+        let bus = pipeline.bus().ok_or(Error::Bus)?;
+        let alive_worker = Arc::clone(&alive);
+        let video = Arc::new(RwLock::new(Internal {
             id,
 
-            bus: pipeline.bus().unwrap(),
+            bus: bus.clone(),
+            streams,
+            selected_streams,
             source: pipeline,
+            video_sink,
             video_filters: VideoFilters::default(),
             alive,
-            worker: Some(worker),
+            resident,
+            suspended: None,
+            volume_fade,
+            worker: None,
 
             width,
             height,
+            has_video,
+            pixel_aspect_ratio,
+            frame_size,
+            orientation,
+            effect: Effect::default(),
+            lut: None,
+            thumbnail_cache: None,
+            cover_art,
             framerate,
             duration,
             speed: 1.0,
             sync_av,
+            user_av_offset: 0,
+            is_live,
 
             hard_volumne: false,
+            downmix: DownmixMode::Off,
+            silence: None,
+
+            preferred_languages: Vec::new(),
+            prefer_sdh: false,
+
+            manifest: Arc::new(Mutex::new(Vec::new())),
+            quality: None,
 
             frame,
+            first_frame,
             upload_frame,
+            frame_version,
+            frame_image: Mutex::new(None),
+            output_range: Arc::new(AtomicU8::new(0)),
             last_frame_time,
             looping: false,
+            segment_looping: false,
+            loops_remaining: None,
             is_eos: false,
             restart_stream: false,
+            unreported: Unreported::default(),
             sync_av_avg: 0,
             sync_av_counter: 0,
-
-            subtitle_text,
+            low_latency,
+            late_frame_policy,
+            latency_estimate: None,
+            buffering: false,
+            chapters,
+            audio_scrubbing: false,
+            scrub_mode: ScrubMode::default(),
+            scrub: None,
+            frame_processor,
+            recorder,
+            events,
+            scene_detector,
+            gates,
+            awaiting_approval,
+
+            subtitle_cue,
+            subtitle_cues: None,
             upload_text,
-        })))
+            subtitle_is_ass,
+        }));
+
+        let video_ref = Arc::downgrade(&video);
+        let worker = std::thread::spawn(move || {
+            while alive_worker.load(Ordering::Acquire) {
+                // waits until the media ends or fails, or the worker is woken, but
+                // still checks `alive` regularly since a stopped pipeline's bus
+                // drops the wake message, e.g., while suspended
+                let Some(msg) = bus.timed_pop_filtered(
+                    gst::ClockTime::from_mseconds(100),
+                    &[
+                        gst::MessageType::Error,
+                        gst::MessageType::Eos,
+                        gst::MessageType::SegmentDone,
+                        gst::MessageType::Application,
+                    ],
+                ) else {
+                    continue;
+                };
+                let Some(video) = video_ref.upgrade() else {
+                    return;
+                };
+                // the video may be shutting down while holding the lock, so it is only tried
+                loop {
+                    if !alive_worker.load(Ordering::Acquire) {
+                        return;
+                    }
+                    if let Ok(mut inner) = video.try_write() {
+                        inner.handle_bus(&msg);
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            }
+        });
+        video.write().map_err(|_| Error::Lock)?.worker = Some(worker);
+
+        Ok(Video(video))
     }
 
     /// Sets the [`VideoFilters`] of the [`Video`].
@@ -570,18 +2382,72 @@ impl Video {
         self.0.write().expect("lock")
     }
 
-    pub(crate) fn get_mut(&mut self) -> impl DerefMut<Target = Internal> + '_ {
-        self.0.get_mut().expect("lock")
+    pub(crate) fn get_mut(&mut self) -> impl DerefMut<Target = Internal> + '_ {
+        // only the worker thread shares the lock, and never blocks on it
+        self.0.write().expect("lock")
+    }
+
+    /// Get the size of the video as `(width, height)`, with non-square pixels
+    /// (e.g., anamorphic DVDs) scaled to their display aspect ratio.
+    pub fn size(&self) -> (i32, i32) {
+        let (width, height, pixel_aspect_ratio) =
+            *self.read().frame_size.lock().expect("lock frame_size");
+        square_pixels(width, height, pixel_aspect_ratio)
+    }
+
+    /// Get the pixel aspect ratio of the video as `(width, height)`; `(1, 1)` for square pixels.
+    pub fn pixel_aspect_ratio(&self) -> (i32, i32) {
+        let (_, _, pixel_aspect_ratio) = *self.read().frame_size.lock().expect("lock frame_size");
+        (pixel_aspect_ratio.numer(), pixel_aspect_ratio.denom())
+    }
+
+    /// Get the orientation the video is displayed in, as tagged by its container.
+    /// [`Video::size`] is the size before the orientation is applied.
+    pub fn orientation(&self) -> Orientation {
+        self.read().orientation
+    }
+
+    /// Get the framerate of the video as frames per second.
+    pub fn framerate(&self) -> f64 {
+        self.read().framerate
+    }
+
+    /// Get if the media has a video stream, or a visualization of its audio,
+    /// see [`VideoBuilder::visualization`]. Audio-only media has the size of
+    /// its [`Video::cover_art`], or a size of zero.
+    pub fn has_video(&self) -> bool {
+        self.read().has_video
+    }
+
+    /// Get the picture attached to audio-only media (e.g., album art), if any.
+    /// It is shown by the [`VideoPlayer`](crate::VideoPlayer) in place of the video.
+    pub fn cover_art(&self) -> Option<img::Handle> {
+        let inner = self.read();
+        let frame = Frame(inner.cover_art.clone()?);
+        let (width, height) = frame.size()?;
+        Some(img::Handle::from_rgba(width, height, frame.rgba()?))
+    }
+
+    /// Get the dynamic range of the surface the video was last rendered to, or
+    /// `None` if it has not been rendered yet.
+    pub fn output_range(&self) -> Option<DynamicRange> {
+        DynamicRange::load(&self.read().output_range)
+    }
+
+    /// Sets the post-processing [`Effect`] applied to the frame when drawn.
+    pub fn set_effect(&mut self, effect: Effect) {
+        self.get_mut().effect = effect;
     }
 
-    /// Get the size/resolution of the video as `(width, height)`.
-    pub fn size(&self) -> (i32, i32) {
-        (self.read().width, self.read().height)
+    /// Get the post-processing [`Effect`] applied to the frame.
+    pub fn effect(&self) -> Effect {
+        self.read().effect
     }
 
-    /// Get the framerate of the video as frames per second.
-    pub fn framerate(&self) -> f64 {
-        self.read().framerate
+    /// Sets the 3D [`CubeLut`] the frame is graded with when drawn, or `None` to remove it.
+    /// It is applied after the [`Effect`].
+    pub fn set_lut(&mut self, lut: Option<CubeLut>) {
+        self.get_mut().lut = lut;
     }
 
     /// Returns the gamma level of the playback. The default gamma level is 1.0.
@@ -694,8 +2560,65 @@ impl Video {
     ///
     /// This uses a linear scale, for example `0.5` is perceived as half as loud.
     pub fn set_volume(&mut self, volume: f64) {
-        self.get_mut().source.set_property("volume", volume);
-        self.set_muted(self.muted()); // for some reason gstreamer unmutes when changing volume?
+        self.get_mut().set_volume(volume)
+    }
+
+    /// Gradually changes the volume to `target` over `duration`, e.g., to fade
+    /// out before pausing rather than cutting the audio off with a click.
+    ///
+    /// The volume changes linearly, replacing any fade in progress. Pipelines
+    /// without a `volume` property (i.e., other than `playbin`) are unaffected.
+    pub fn fade_volume_to(&mut self, target: f64, duration: Duration) {
+        let inner = self.get_mut();
+        if !inner.source.has_property("volume", None) {
+            return;
+        }
+        let from = inner.source.property::<f64>("volume");
+        let Ok(mut fade) = inner.volume_fade.lock() else {
+            return;
+        };
+        // the thread stepping a fade in progress takes over the new one
+        let stepping = fade.is_some();
+        *fade = Some(VolumeFade {
+            from,
+            to: target,
+            start: Instant::now(),
+            duration,
+        });
+        drop(fade);
+        if stepping {
+            return;
+        }
+
+        // steps the volume until the fade completes or is cancelled
+        let volume_fade = Arc::clone(&inner.volume_fade);
+        let pipeline = inner.source.clone();
+        std::thread::spawn(move || {
+            loop {
+                let Ok(mut fade) = volume_fade.lock() else {
+                    return;
+                };
+                let Some(current) = *fade else {
+                    return;
+                };
+                let (volume, done) = current.volume_at(Instant::now());
+                set_volume(&pipeline, volume);
+                if done {
+                    *fade = None;
+                    return;
+                }
+                drop(fade);
+                std::thread::sleep(Duration::from_millis(16));
+            }
+        });
+    }
+
+    /// Get if the volume is being faded, see [`Video::fade_volume_to`].
+    pub fn is_fading(&self) -> bool {
+        self.read()
+            .volume_fade
+            .lock()
+            .is_ok_and(|fade| fade.is_some())
     }
 
     /// Get the volume multiplier of the audio.
@@ -703,6 +2626,39 @@ impl Video {
         self.read().source.property("volume")
     }
 
+    /// Mixes the audio down to fewer channels, e.g., so that the centre channel
+    /// of 5.1 audio (which usually carries the dialogue) isn't lost on stereo
+    /// speakers that only play the front channels.
+    ///
+    /// The channels are mixed with `audioconvert`'s standard matrix, which keeps
+    /// the centre and surround channels in both sides. Audio with fewer
+    /// channels than requested is left alone. Only `playbin` pipelines created
+    /// by [`Video::new`] or [`VideoBuilder`](crate::VideoBuilder) can be mixed down.
+    pub fn set_audio_downmix(&mut self, mode: DownmixMode) -> Result<(), Error> {
+        let inner = self.get_mut();
+        let downmix = inner
+            .source
+            .by_name("downmix")
+            .ok_or(Error::Cast("downmix capsfilter"))?;
+        let caps = match mode {
+            DownmixMode::Off => gst::Caps::new_any(),
+            DownmixMode::Stereo => gst::Caps::builder("audio/x-raw")
+                .field("channels", gst::IntRange::new(1, 2))
+                .build(),
+            DownmixMode::Mono => gst::Caps::builder("audio/x-raw")
+                .field("channels", 1)
+                .build(),
+        };
+        downmix.set_property("caps", caps);
+        inner.downmix = mode;
+        Ok(())
+    }
+
+    /// Get how the audio channels are mixed down, see [`Video::set_audio_downmix`].
+    pub fn audio_downmix(&self) -> DownmixMode {
+        self.read().downmix
+    }
+
     /// Toggles the use of hardware/software volume.
     pub fn toggle_hardware_volume(&mut self) {
         self.get_mut().toggle_hardware_volume()
@@ -725,11 +2681,8 @@ impl Video {
 
     /// Gets the current audio of the media if any.
     pub fn get_audio(&self) -> Option<AudioTag> {
-        let pipeline = &self.read().source;
-
-        let id = pipeline.property::<i32>("current-audio");
-
-        get_audio(pipeline, id)
+        let inner = self.read();
+        inner.audio_track(inner.current_track(gst::StreamType::AUDIO))
     }
 
     /// Sets the audio playback the current media.
@@ -737,12 +2690,44 @@ impl Video {
         self.get_mut().set_audio(audio)
     }
 
+    /// Sets the preferred languages of the audio and subtitle tracks as ISO 639
+    /// codes (e.g., `&["de", "en"]`), from most to least preferred.
+    ///
+    /// The best matching tracks are selected right away, and whenever new media
+    /// is loaded. Tracks are left alone if none match.
+    pub fn set_preferred_languages(&mut self, languages: &[&str]) {
+        let mut inner = self.get_mut();
+        inner.preferred_languages = languages
+            .iter()
+            .map(|&language| language.to_owned())
+            .collect();
+        inner.select_preferred_tracks();
+    }
+
+    /// Returns the preferred languages of the audio and subtitle tracks.
+    pub fn preferred_languages(&self) -> Vec<String> {
+        self.read().preferred_languages.clone()
+    }
+
+    /// Sets whether subtitles for the deaf and hard of hearing (SDH) are
+    /// preferred over regular subtitles of the same language.
+    pub fn set_prefer_sdh(&mut self, prefer_sdh: bool) {
+        let mut inner = self.get_mut();
+        inner.prefer_sdh = prefer_sdh;
+        inner.select_preferred_tracks();
+    }
+
+    /// Returns whether SDH subtitles are preferred.
+    pub fn prefer_sdh(&self) -> bool {
+        self.read().prefer_sdh
+    }
+
     /// Returns a list of available audio for the media.
     pub fn available_audio(&self) -> Vec<AudioTag> {
-        let pipeline = &self.read().source;
-        let n = pipeline.property::<i32>("n-audio");
+        let inner = self.read();
+        let n = inner.track_count(gst::StreamType::AUDIO);
 
-        (0..n).filter_map(|id| get_audio(pipeline, id)).collect()
+        (0..n).filter_map(|id| inner.audio_track(id)).collect()
     }
 
     /// Get if the stream ended or not.
@@ -750,81 +2735,672 @@ impl Video {
         self.read().is_eos
     }
 
-    /// Get if the media will loop or not.
-    pub fn looping(&self) -> bool {
-        self.read().looping
+    /// Get if the media will loop or not.
+    pub fn looping(&self) -> bool {
+        self.read().looping
+    }
+
+    /// Set if the media will loop or not.
+    ///
+    /// Seekable media loops seamlessly with segment seeks; other media restarts
+    /// once it ended.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.get_mut().set_looping(looping);
+    }
+
+    /// Sets how many more times the media loops before it ends, or `None` to
+    /// loop indefinitely. Enables looping unless the count is zero.
+    ///
+    /// For example, a count of 2 plays the media three times in total from here.
+    pub fn set_loop_count(&mut self, count: Option<u32>) {
+        let mut inner = self.get_mut();
+        inner.loops_remaining = count;
+        inner.set_looping(count != Some(0));
+    }
+
+    /// Get how many more times the media loops before it ends, or `None` if it
+    /// loops indefinitely (or not at all, see [`Video::looping`]).
+    pub fn loops_remaining(&self) -> Option<u32> {
+        self.read().loops_remaining
+    }
+
+    /// Set if the media is paused or not.
+    pub fn set_paused(&mut self, paused: bool) {
+        if let Err(err) = self.get_mut().set_paused(paused) {
+            log::error!("cannot set paused to {paused}: {err}");
+        }
+    }
+
+    /// Get if the media is paused or not.
+    pub fn paused(&self) -> bool {
+        self.read().paused()
+    }
+
+    /// Get the playback status, which also accounts for buffering and stalls.
+    pub fn status(&self) -> PlaybackStatus {
+        self.read().status()
+    }
+
+    /// Adds a gate at the given position. When playback reaches it, the media
+    /// is paused and hidden until [`Video::approve`] is called, e.g., after a
+    /// parent confirms. Each gate only triggers once.
+    pub fn add_gate(&mut self, position: Duration) {
+        let inner = self.get_mut();
+        let mut gates = inner.gates.lock().expect("lock gates");
+        gates.push(position);
+        gates.sort();
+    }
+
+    /// Removes all gates which have not been reached yet.
+    pub fn clear_gates(&mut self) {
+        self.get_mut().gates.lock().expect("lock gates").clear();
+    }
+
+    /// Returns the gates which have not been reached yet.
+    pub fn gates(&self) -> Vec<Duration> {
+        self.read().gates.lock().expect("lock gates").clone()
+    }
+
+    /// Pauses and hides the media right away until [`Video::approve`] is called.
+    /// The pipeline stays prerolled so that playback resumes instantly.
+    pub fn require_approval(&mut self) {
+        let mut inner = self.get_mut();
+        if let Err(err) = inner.set_paused(true) {
+            log::error!("cannot pause until approved: {err}");
+        }
+        inner.awaiting_approval.store(true, Ordering::SeqCst);
+    }
+
+    /// Get if playback is blocked until [`Video::approve`] is called.
+    pub fn awaiting_approval(&self) -> bool {
+        self.read().awaiting_approval.load(Ordering::SeqCst)
+    }
+
+    /// Approves playback blocked by a gate or [`Video::require_approval`], and
+    /// resumes it.
+    pub fn approve(&mut self) {
+        let mut inner = self.get_mut();
+        if inner.awaiting_approval.swap(false, Ordering::SeqCst)
+            && let Err(err) = inner.set_paused(false)
+        {
+            log::error!("cannot resume once approved: {err}");
+        }
+    }
+
+    /// Jumps to a specific position in the media.
+    /// Passing `true` to the `accurate` parameter will result in more accurate seeking,
+    /// however, it is also slower. For most seeks (e.g., scrubbing) this is not needed.
+    pub fn seek(&mut self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
+        self.get_mut().seek(position, accurate)
+    }
+
+    /// Jumps to a specific position in the media, with finer control over how
+    /// than [`Video::seek`], see [`SeekOptions`].
+    pub fn seek_with(
+        &mut self,
+        position: impl Into<Position>,
+        options: SeekOptions,
+    ) -> Result<(), Error> {
+        self.get_mut().seek_with(position, options)
+    }
+
+    /// Jumps to the keyframe nearest to `position`, which is much faster than an
+    /// accurate seek, but may land a few seconds away from it.
+    pub fn seek_keyframe(&mut self, position: Duration) -> Result<(), Error> {
+        self.get_mut().seek_keyframe(position)
+    }
+
+    /// Sets how [`Video::scrub`] moves through the media, see [`ScrubMode`].
+    /// Has no effect while [`Video::set_audio_scrubbing`] is enabled.
+    pub fn set_scrub_mode(&mut self, scrub_mode: ScrubMode) {
+        self.get_mut().scrub_mode = scrub_mode;
+    }
+
+    /// Get how [`Video::scrub`] moves through the media.
+    pub fn scrub_mode(&self) -> ScrubMode {
+        self.read().scrub_mode
+    }
+
+    /// Sets if short audio snippets are played while scrubbing with [`Video::scrub`],
+    /// which helps finding e.g. dialogue by ear.
+    pub fn set_audio_scrubbing(&mut self, audio_scrubbing: bool) {
+        self.get_mut().audio_scrubbing = audio_scrubbing;
+    }
+
+    /// Get if audio snippets are played while scrubbing.
+    pub fn audio_scrubbing(&self) -> bool {
+        self.read().audio_scrubbing
+    }
+
+    /// Moves to `position` while the user drags a seek bar. Call [`Video::end_scrub`]
+    /// once the drag ends.
+    ///
+    /// With [`Video::set_audio_scrubbing`] enabled, a short snippet of audio is
+    /// played at each position; otherwise the audio is muted for the duration of
+    /// the drag. The playback state is restored by [`Video::end_scrub`].
+    ///
+    /// Audio snippets require a [`VideoPlayer`](crate::VideoPlayer) showing the video.
+    pub fn scrub(&mut self, position: Duration) -> Result<(), Error> {
+        self.get_mut().scrub(position)
+    }
+
+    /// Ends scrubbing at `position`, restoring the paused and muted state from
+    /// before [`Video::scrub`] was first called.
+    pub fn end_scrub(&mut self, position: Duration) -> Result<(), Error> {
+        self.get_mut().end_scrub(position)
+    }
+
+    /// Steps forward exactly one frame in playback.
+    /// This can be especially useful while the video is paused to make pipeline changes visible, without resuming playback.
+    pub fn step_one_frame(&mut self) {
+        self.get_mut().source.send_event(gst::event::Step::new(
+            gst::GenericFormattedValue::Buffers(Some(gst::format::Buffers::from_u64(1))),
+            1.0,
+            true,
+            false,
+        ));
+    }
+
+    /// Set the playback speed of the media.
+    /// The default speed is `1.0`.
+    pub fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
+        self.get_mut().set_speed(speed)
+    }
+
+    /// Get the current playback speed.
+    pub fn speed(&self) -> f64 {
+        self.read().speed
+    }
+
+    /// Get the current playback position in time.
+    pub fn position(&self) -> Duration {
+        Duration::from_nanos(
+            self.read()
+                .source
+                .query_position::<gst::ClockTime>()
+                .map_or(0, |pos| pos.nseconds()),
+        )
+    }
+
+    /// Get the current time of the pipeline clock.
+    ///
+    /// Unlike [`Video::position`], this does not query the pipeline elements, so it
+    /// is suitable for aligning external events to the clock driving playback.
+    /// Returns `None` if the pipeline has no clock (e.g., before prerolling).
+    pub fn clock_time(&self) -> Option<Duration> {
+        self.read()
+            .source
+            .current_clock_time()
+            .map(|time| Duration::from_nanos(time.nseconds()))
+    }
+
+    /// Get the current running time of the pipeline, i.e., the clock time minus
+    /// the base time. The running time does not advance while paused.
+    ///
+    /// Returns `None` if the pipeline has no clock (e.g., before prerolling).
+    pub fn running_time(&self) -> Option<Duration> {
+        self.read()
+            .source
+            .current_running_time()
+            .map(|time| Duration::from_nanos(time.nseconds()))
+    }
+
+    /// Get the media duration.
+    ///
+    /// Live sources have no duration and always report zero.
+    pub fn duration(&self) -> Duration {
+        self.read().duration
+    }
+
+    /// Get the rendered and dropped frame counts, the decoder in use and other
+    /// statistics of the playback.
+    pub fn stats(&self) -> PlaybackStats {
+        let inner = self.read();
+        stats::collect(&inner.source, &inner.video_sink)
+    }
+
+    /// Get the packet loss, jitter and RTCP statistics of a packet-based source
+    /// (e.g., RTSP or RTP over UDP), or `None` for other sources.
+    pub fn stream_health(&self) -> Option<StreamHealth> {
+        health::collect(&self.read().source)
+    }
+
+    /// Get the chapters of the media, sorted by their start. Empty if the media
+    /// has no table of contents.
+    pub fn chapters(&self) -> Vec<Chapter> {
+        self.read().chapters.clone()
+    }
+
+    /// Get the chapter playing at `position`, if any.
+    pub fn chapter_at(&self, position: Duration) -> Option<Chapter> {
+        let inner = self.read();
+        inner
+            .chapters
+            .iter()
+            .rev()
+            .find(|chapter| {
+                chapter.start <= position && chapter.end.is_none_or(|end| position < end)
+            })
+            .cloned()
+    }
+
+    /// Get if the media is a live source (e.g., an RTSP stream or a capture device).
+    ///
+    /// Live sources cannot be meaningfully seeked or looped and have no duration.
+    pub fn is_live(&self) -> bool {
+        self.read().is_live
+    }
+
+    /// Enables a minimal latency rendering path for interactive sources (e.g.,
+    /// game streams or remote desktops).
+    ///
+    /// Frames are presented as soon as they are decoded rather than being
+    /// synchronised to the clock, stale frames are dropped in favour of the
+    /// newest one, and audio/video latency compensation is disabled.
+    pub fn set_low_latency(&mut self, low_latency: bool) {
+        self.get_mut().set_low_latency(low_latency)
+    }
+
+    /// Get if the minimal latency rendering path is enabled.
+    pub fn low_latency(&self) -> bool {
+        self.read().low_latency.load(Ordering::SeqCst)
+    }
+
+    /// Sets what happens to decoded frames when the UI falls behind.
+    ///
+    /// Frames are taken from the pipeline as soon as they are decoded, and at
+    /// most one of them waits to be displayed.
+    ///
+    /// Live monitoring applications may prefer [`LateFramePolicy::DropOldest`]
+    /// to always show the freshest picture, whereas archival players may prefer
+    /// [`LateFramePolicy::Block`] so that no frame is skipped.
+    pub fn set_late_frame_policy(&mut self, policy: LateFramePolicy) {
+        self.get_mut().set_late_frame_policy(policy)
+    }
+
+    /// Get what happens to decoded frames when the UI falls behind.
+    pub fn late_frame_policy(&self) -> LateFramePolicy {
+        LateFramePolicy::load(&self.read().late_frame_policy)
+    }
+
+    /// Sets how late a frame may be before it is dropped by the pipeline
+    /// instead of being presented. `None` never drops late frames.
+    pub fn set_max_lateness(&mut self, max_lateness: Option<Duration>) {
+        self.get_mut()
+            .video_sink
+            .set_max_lateness(max_lateness.map_or(-1, |lateness| lateness.as_nanos() as i64));
+    }
+
+    /// Get how late a frame may be before it is dropped by the pipeline.
+    pub fn max_lateness(&self) -> Option<Duration> {
+        u64::try_from(self.read().video_sink.max_lateness())
+            .ok()
+            .map(Duration::from_nanos)
+    }
+
+    /// Queries the latency of the pipeline as `(minimum, maximum)`, where no
+    /// maximum means it is unbounded. Returns `None` if the query fails, e.g.,
+    /// before the pipeline prerolled.
+    ///
+    /// Only live sources have a latency, see [`Video::is_live`]; for other media
+    /// both are zero.
+    pub fn latency(&self) -> Option<(Duration, Option<Duration>)> {
+        let mut query = gst::query::Latency::new();
+        if !self.read().source.query(&mut query) {
+            return None;
+        }
+        let (_, min, max) = query.result();
+        Some((
+            Duration::from_nanos(min.nseconds()),
+            max.map(|max| Duration::from_nanos(max.nseconds())),
+        ))
+    }
+
+    /// Get an estimate of the glass-to-glass latency of the last presented
+    /// frame, i.e., the time between its capture and its presentation.
+    ///
+    /// Only available while [`Video::set_low_latency`] is enabled, and only
+    /// meaningful for live sources.
+    pub fn latency_estimate(&self) -> Option<Duration> {
+        self.read().latency_estimate
+    }
+
+    /// Sets a processor which is handed every decoded frame before it is
+    /// uploaded for display, e.g., to blur regions of the picture.
+    ///
+    /// The processor runs on the thread pulling frames from the pipeline, not
+    /// on the UI thread. Every processed frame is copied once so that it can be
+    /// written to, and the processor must return well within a frame interval
+    /// (about 16 ms at 60 fps); a slow processor delays every frame after it and
+    /// eventually causes frames to be dropped.
+    pub fn set_frame_processor<F>(&mut self, processor: F)
+    where
+        F: FnMut(&mut RawFrame<'_>) + Send + 'static,
+    {
+        *self
+            .get_mut()
+            .frame_processor
+            .lock()
+            .expect("lock frame_processor") = Some(FrameProcessor(Box::new(processor)));
+    }
+
+    /// Removes the frame processor set with [`Video::set_frame_processor`].
+    pub fn clear_frame_processor(&mut self) {
+        *self
+            .get_mut()
+            .frame_processor
+            .lock()
+            .expect("lock frame_processor") = None;
+    }
+
+    /// Detects scene changes (i.e., cuts) in the frames as they are played,
+    /// which are sent with [`VideoPlayer::on_scene_change`](crate::VideoPlayer::on_scene_change),
+    /// e.g., to place chapter markers. `None` stops detecting them.
+    ///
+    /// Frames are compared by their brightness histograms; `threshold` is the
+    /// fraction (`0.0..=1.0`) of the histogram which has to change between two
+    /// frames. `0.4` catches most hard cuts, while lower thresholds also catch
+    /// cuts between similar shots at the cost of false positives (e.g., flashes).
+    pub fn set_scene_detection(&mut self, threshold: Option<f64>) {
+        *self
+            .get_mut()
+            .scene_detector
+            .lock()
+            .expect("lock scene_detector") = threshold.map(SceneDetector::new);
+    }
+
+    /// Starts encoding the frames being played to a file at `path`, while
+    /// playback continues. Any recording in progress is stopped first.
+    ///
+    /// Only the video is recorded. Frames are recorded as they are presented, so
+    /// pauses and seeks end up in the file as they were watched.
+    pub fn start_recording(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+        settings: &RecordingSettings,
+    ) -> Result<(), Error> {
+        self.stop_recording()?;
+        let recorder = Recorder::new(path.as_ref(), settings)?;
+        *self.get_mut().recorder.lock().map_err(|_| Error::Lock)? = Some(recorder);
+        Ok(())
+    }
+
+    /// Stops the recording started with [`Video::start_recording`], blocking
+    /// until the file is finalized.
+    pub fn stop_recording(&mut self) -> Result<(), Error> {
+        let recorder = self
+            .get_mut()
+            .recorder
+            .lock()
+            .map_err(|_| Error::Lock)?
+            .take();
+        recorder.map_or(Ok(()), Recorder::finish)
+    }
+
+    /// Transcodes the range `start..end` of the media to a file at `path`, e.g.,
+    /// to trim a highlight out of a long recording. Playback is not affected.
+    ///
+    /// `on_progress` is called from another thread with the fraction (`0.0..=1.0`)
+    /// exported so far. The returned future can be run with `iced::Task::perform`.
+    pub fn export_clip<F>(
+        &self,
+        start: Duration,
+        end: Duration,
+        path: impl Into<std::path::PathBuf>,
+        preset: &ExportPreset,
+        on_progress: F,
+    ) -> impl std::future::Future<Output = Result<(), Error>> + Send + 'static
+    where
+        F: Fn(f64) + Send + 'static,
+    {
+        let uri = self.read().uri();
+        let path = path.into();
+        let preset = preset.clone();
+
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            let result = match uri {
+                Some(uri) => recording::export(&uri, start, end, &path, &preset, on_progress),
+                None => Err(Error::Uri),
+            };
+            let _ = sender.send(result);
+        });
+        async move { receiver.await.map_err(|_| Error::Thread)? }
+    }
+
+    /// Decodes the frames in `range` as RGBA on a separate pipeline, e.g., for
+    /// computer vision, without affecting playback. At most one frame is
+    /// yielded per `step`; pass [`Duration::ZERO`] for every frame.
+    pub fn frames(
+        &self,
+        range: std::ops::Range<Duration>,
+        step: Duration,
+    ) -> Result<Frames, Error> {
+        let uri = self.read().uri().ok_or(Error::Uri)?;
+        Frames::new(&uri, range, step)
+    }
+
+    /// Get if a recording is in progress.
+    pub fn is_recording(&self) -> bool {
+        self.read()
+            .recorder
+            .lock()
+            .is_ok_and(|recorder| recorder.is_some())
+    }
+
+    /// Set the latency the pipeline should use, overriding the latency
+    /// negotiated between the elements. Only relevant for live sources.
+    pub fn set_latency(&mut self, latency: Duration) {
+        self.get_mut()
+            .source
+            .set_latency(gst::ClockTime::from_nseconds(latency.as_nanos() as _));
+    }
+
+    /// Enables progressive download of network media to a temporary file, so
+    /// that already downloaded parts can be seeked to without refetching them.
+    ///
+    /// Takes effect the next time the media is loaded.
+    pub fn set_download(&mut self, download: bool) {
+        self.get_mut().set_flag("download", download);
+    }
+
+    /// Get if progressive download is enabled.
+    pub fn download(&self) -> bool {
+        self.read().flag("download")
+    }
+
+    /// Set the maximum size in bytes of the in-memory ring buffer used to
+    /// buffer network media. Zero disables the ring buffer.
+    pub fn set_ring_buffer_max_size(&mut self, size: u64) {
+        self.get_mut()
+            .source
+            .set_property("ring-buffer-max-size", size);
+    }
+
+    /// Get the maximum size in bytes of the in-memory ring buffer.
+    pub fn ring_buffer_max_size(&self) -> u64 {
+        self.read().source.property("ring-buffer-max-size")
+    }
+
+    /// Get the ranges of the media which are buffered (downloaded) as
+    /// `(start, end)` pairs, e.g., to shade a seek bar.
+    ///
+    /// Returns an empty list if the media is not being buffered (e.g., local files).
+    pub fn buffered_ranges(&self) -> Vec<(Duration, Duration)> {
+        // GST_FORMAT_PERCENT_MAX
+        const PERCENT_MAX: u128 = 1_000_000;
+
+        let inner = self.read();
+
+        let mut query = gst::query::Buffering::new(gst::Format::Percent);
+        if !inner.source.query(&mut query) {
+            return Vec::new();
+        }
+
+        let to_duration = |value: gst::GenericFormattedValue| match value {
+            gst::GenericFormattedValue::Time(Some(time)) => {
+                Some(Duration::from_nanos(time.nseconds()))
+            }
+            gst::GenericFormattedValue::Percent(Some(_)) => {
+                let ppm = value.value().clamp(0, PERCENT_MAX as i64) as u128;
+                Some(Duration::from_nanos(
+                    (inner.duration.as_nanos() * ppm / PERCENT_MAX) as u64,
+                ))
+            }
+            _ => None,
+        };
+
+        query
+            .ranges()
+            .into_iter()
+            .filter_map(|(start, stop)| Some((to_duration(start)?, to_duration(stop)?)))
+            .collect()
+    }
+
+    /// Get if the media can be seeked, see [`Video::seekable_range`].
+    pub fn seekable(&self) -> bool {
+        self.seekable_range().is_some()
+    }
+
+    /// Get the range of the media which can be seeked to as `(start, end)`, or
+    /// `None` if the media cannot be seeked at all (e.g., most live sources).
+    pub fn seekable_range(&self) -> Option<(Duration, Duration)> {
+        let inner = self.read();
+
+        let mut query = gst::query::Seeking::new(gst::Format::Time);
+        if !inner.source.query(&mut query) {
+            return None;
+        }
+
+        let (seekable, start, end) = query.result();
+        if !seekable {
+            return None;
+        }
+
+        let to_duration = |value: gst::GenericFormattedValue| match value {
+            gst::GenericFormattedValue::Time(Some(time)) => {
+                Some(Duration::from_nanos(time.nseconds()))
+            }
+            _ => None,
+        };
+
+        Some((
+            to_duration(start).unwrap_or(Duration::ZERO),
+            to_duration(end).unwrap_or(inner.duration),
+        ))
     }
 
-    /// Set if the media will loop or not.
-    pub fn set_looping(&mut self, looping: bool) {
-        self.get_mut().looping = looping;
+    /// Restarts a stream; seeks to the first frame and unpauses, sets the `eos` flag to false.
+    pub fn restart_stream(&mut self) -> Result<(), Error> {
+        self.get_mut().restart_stream()
     }
 
-    /// Set if the media is paused or not.
-    pub fn set_paused(&mut self, paused: bool) {
-        self.get_mut().set_paused(paused)
-    }
+    /// Loads the media at `uri` in place of the current media, e.g., the next
+    /// item of a playlist, keeping the paused state, volume and speed.
+    ///
+    /// Unlike creating a new [`Video`], the worker thread and the texture of the
+    /// video are reused. This blocks while the pipeline prerolls the new media.
+    /// Only `playbin` pipelines can load another URI.
+    pub fn set_uri(&mut self, uri: &url::Url) -> Result<(), Error> {
+        let paused = self.paused();
+        let mut inner = self.get_mut();
+        if !inner.source.has_property("uri", None) {
+            return Err(Error::Uri);
+        }
 
-    /// Get if the media is paused or not.
-    pub fn paused(&self) -> bool {
-        self.read().paused()
+        inner.source.set_state(gst::State::Ready)?;
+        // the new media is cached as it prerolls
+        *inner.first_frame.lock().map_err(|_| Error::Lock)? = None;
+        inner.source.set_property("uri", uri.as_str());
+        inner.source.set_state(gst::State::Paused)?;
+        inner.source.state(gst::ClockTime::from_seconds(5)).0?;
+        inner.load_media_info()?;
+
+        inner.is_eos = false;
+        inner.restart_stream = false;
+        inner.subtitle_cues = None;
+        *inner.subtitle_cue.lock().map_err(|_| Error::Lock)? = None;
+        inner.upload_text.store(true, Ordering::SeqCst);
+        inner.select_preferred_tracks();
+        inner.set_paused(paused)
     }
 
-    /// Jumps to a specific position in the media.
-    /// Passing `true` to the `accurate` parameter will result in more accurate seeking,
-    /// however, it is also slower. For most seeks (e.g., scrubbing) this is not needed.
-    pub fn seek(&mut self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
-        self.get_mut().seek(position, accurate)
+    /// Stops the video and releases its decoding resources, returning any error
+    /// on the way rather than only logging it as dropping the video does.
+    ///
+    /// Once this returns, the pipeline is stopped (closing the media, e.g., its
+    /// file) and the worker thread has exited.
+    ///
+    /// **Note:** The texture of the video belongs to the renderer, which only
+    /// lets it go when it next renders a frame, so it is not released here.
+    pub fn close(mut self) -> Result<(), Error> {
+        self.0.write().map_err(|_| Error::Lock)?.shutdown()
     }
 
-    /// Steps forward exactly one frame in playback.
-    /// This can be especially useful while the video is paused to make pipeline changes visible, without resuming playback.
-    pub fn step_one_frame(&mut self) {
-        self.get_mut().source.send_event(gst::event::Step::new(
-            gst::GenericFormattedValue::Buffers(Some(gst::format::Buffers::from_u64(1))),
-            1.0,
-            true,
-            false,
-        ));
-    }
+    /// Parks the video to free its resources, e.g., while it is off-screen.
+    ///
+    /// The pipeline is stopped, which releases its decoders, and the texture of
+    /// the video is let go the next time the renderer renders. The video shows nothing
+    /// until [`Video::resume`] is called. Does nothing if already suspended.
+    pub fn suspend(&mut self) -> Result<(), Error> {
+        let position = self.position();
+        let mut inner = self.get_mut();
+        if inner.suspended.is_some() {
+            return Ok(());
+        }
 
-    /// Set the playback speed of the media.
-    /// The default speed is `1.0`.
-    pub fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
-        self.get_mut().set_speed(speed)
+        let suspended = Suspended {
+            position,
+            paused: inner.paused() || inner.is_eos,
+            audio: inner.current_track(gst::StreamType::AUDIO),
+            text: inner.current_track(gst::StreamType::TEXT),
+        };
+        inner.resident.store(false, Ordering::SeqCst);
+        inner.upload_frame.notify();
+        inner.source.set_state(gst::State::Null)?;
+        *inner.frame.lock().map_err(|_| Error::Lock)? = Frame::empty();
+        *inner.frame_image.lock().map_err(|_| Error::Lock)? = None;
+        inner.suspended = Some(suspended);
+        Ok(())
     }
 
-    /// Get the current playback speed.
-    pub fn speed(&self) -> f64 {
-        self.read().speed
-    }
+    /// Restores a video parked by [`Video::suspend`] to its position, speed,
+    /// volume, track selection and paused state. Does nothing if not suspended.
+    ///
+    /// This blocks while the pipeline prerolls again.
+    pub fn resume(&mut self) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        let Some(suspended) = inner.suspended.take() else {
+            return Ok(());
+        };
 
-    /// Get the current playback position in time.
-    pub fn position(&self) -> Duration {
-        Duration::from_nanos(
-            self.read()
-                .source
-                .query_position::<gst::ClockTime>()
-                .map_or(0, |pos| pos.nseconds()),
-        )
-    }
+        inner.source.set_state(gst::State::Paused)?;
+        inner.source.state(gst::ClockTime::from_seconds(5)).0?;
+        inner.resident.store(true, Ordering::SeqCst);
 
-    /// Get the media duration.
-    pub fn duration(&self) -> Duration {
-        self.read().duration
+        // the volume is kept by playbin, while the rest is reset by stopping it
+        inner.seek(suspended.position, true)?;
+        if suspended.audio >= 0 {
+            inner.select_track(gst::StreamType::AUDIO, suspended.audio);
+        }
+        if suspended.text >= 0 {
+            inner.select_track(gst::StreamType::TEXT, suspended.text);
+        }
+        inner.is_eos = false;
+        inner.set_paused(suspended.paused)
     }
 
-    /// Restarts a stream; seeks to the first frame and unpauses, sets the `eos` flag to false.
-    pub fn restart_stream(&mut self) -> Result<(), Error> {
-        self.get_mut().restart_stream()
+    /// Get if the video is parked by [`Video::suspend`].
+    pub fn is_suspended(&self) -> bool {
+        self.read().suspended.is_some()
     }
 
     /// Returns a list of available subtitles for the media.
     pub fn available_subtitles(&self) -> Vec<TextTag> {
-        let pipeline = &self.read().source;
-        let n = pipeline.property::<i32>("n-text");
+        let inner = self.read();
+        let n = inner.track_count(gst::StreamType::TEXT);
 
-        (0..n).filter_map(|id| get_text(pipeline, id)).collect()
+        (0..n).filter_map(|id| inner.text_track(id)).collect()
     }
 
     /// Sets the subtitle to be shown for the media.
@@ -834,11 +3410,8 @@ impl Video {
 
     /// Gets the current subtitle of the media, if any.
     pub fn get_text(&self) -> Option<TextTag> {
-        let pipeline = &self.read().source;
-
-        let id = pipeline.property::<i32>("current-text");
-
-        get_text(pipeline, id)
+        let inner = self.read();
+        inner.text_track(inner.current_track(gst::StreamType::TEXT))
     }
 
     /// Set the subtitle URL to display.
@@ -847,8 +3420,69 @@ impl Video {
         let mut inner = self.get_mut();
         inner.source.set_state(gst::State::Ready)?;
         inner.source.set_property("suburi", url.as_str());
-        inner.set_paused(paused);
-        Ok(())
+        inner.subtitle_cues = None;
+        inner.set_paused(paused)
+    }
+
+    /// Returns all cues of the external subtitle file, see [`Video::set_subtitle_url`],
+    /// e.g., to search the dialogue. Returns an empty list if no file is set.
+    ///
+    /// The file is parsed once, on the first call.
+    pub fn subtitle_cues(&mut self) -> Result<Vec<SubtitleCue>, Error> {
+        let Some(url) = self.subtitle_url() else {
+            return Ok(Vec::new());
+        };
+        let mut inner = self.get_mut();
+        if inner.subtitle_cues.is_none() {
+            inner.subtitle_cues = Some(subtitle::load_cues(&url)?);
+        }
+        Ok(inner.subtitle_cues.clone().unwrap_or_default())
+    }
+
+    /// Seeks to the start of the cue at `index` in [`Video::subtitle_cues`].
+    pub fn seek_to_cue(&mut self, index: usize) -> Result<(), Error> {
+        let cue = self
+            .subtitle_cues()?
+            .into_iter()
+            .nth(index)
+            .ok_or(Error::Cue(index))?;
+        self.seek(cue.start, true)
+    }
+
+    /// Seeks to the start of the next subtitle cue. Returns `false` if there is none.
+    ///
+    /// Needs an external subtitle file, see [`Video::subtitle_cues`], as the cues
+    /// of embedded tracks are only known once they are shown.
+    pub fn seek_next_subtitle(&mut self) -> Result<bool, Error> {
+        let position = self.position();
+        let cues = self.subtitle_cues()?;
+        match subtitle::next_cue(&cues, position) {
+            Some(cue) => self.seek(cue.start, true).map(|_| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Seeks to the start of the previous subtitle cue, or replays the current
+    /// one if it started less than half a second ago. Returns `false` if there is none.
+    ///
+    /// Without an external subtitle file, see [`Video::subtitle_cues`], this can
+    /// only replay the last cue shown from the embedded track.
+    pub fn seek_previous_subtitle(&mut self) -> Result<bool, Error> {
+        let position = self.position();
+        let mut cues = self.subtitle_cues()?;
+        if cues.is_empty() {
+            cues.extend(
+                self.read()
+                    .subtitle_cue
+                    .lock()
+                    .map_err(|_| Error::Lock)?
+                    .clone(),
+            );
+        }
+        match subtitle::previous_cue(&cues, position, PREVIOUS_CUE_GRACE) {
+            Some(cue) => self.seek(cue.start, true).map(|_| true),
+            None => Ok(false),
+        }
     }
 
     /// Get the current subtitle URL.
@@ -862,72 +3496,359 @@ impl Video {
         .ok()
     }
 
-    /// Control the synchonisation offset between the text and video streams in 
+    /// Control the synchonisation offset between the text and video streams in
     /// nano seconds.
     ///
-    /// Positive values make the text ahead of the video, and negative values 
+    /// Positive values make the text ahead of the video, and negative values
     /// make the text go behind the video.
-    pub fn set_text_offset(&mut self, offset: i64){
+    pub fn set_text_offset(&mut self, offset: i64) {
         self.get_mut().set_text_offset(offset);
     }
 
+    /// Get the synchonisation offset between the text and video streams in
+    /// nano seconds, see [`Video::set_text_offset`].
+    ///
+    /// The offset is applied by the pipeline, to embedded and external
+    /// subtitles alike, without reloading them.
+    pub fn text_offset(&self) -> i64 {
+        -self.read().source.property::<i64>("text-offset")
+    }
+
+    /// Control the synchronisation offset between the audio and video streams
+    /// in nano seconds, e.g., to fix badly muxed media.
+    ///
+    /// Positive values make the audio ahead of the video, and negative values
+    /// make the audio go behind the video. This is applied on top of the
+    /// compensation for the latency of presenting frames. Does nothing for
+    /// pipelines without an `av-offset` property (i.e., other than `playbin`).
+    pub fn set_av_offset(&mut self, offset: i64) {
+        let mut inner = self.get_mut();
+        if inner.sync_av {
+            inner.user_av_offset = offset;
+            inner.apply_av_offset();
+        }
+    }
+
+    /// Get the synchronisation offset between the audio and video streams in
+    /// nano seconds, see [`Video::set_av_offset`].
+    pub fn av_offset(&self) -> i64 {
+        self.read().user_av_offset
+    }
+
+    /// Returns the quality variants of an adaptive (HLS/DASH) stream, from
+    /// highest to lowest bitrate.
+    ///
+    /// Returns an empty list for non-adaptive media, or if the variants are not
+    /// known yet.
+    pub fn available_qualities(&self) -> Vec<Quality> {
+        let manifest = self.read().manifest.lock().map(|m| m.clone());
+        manifest
+            .map(|manifest| adaptive::parse_manifest(&String::from_utf8_lossy(&manifest)))
+            .unwrap_or_default()
+    }
+
+    /// Pins the quality of an adaptive (HLS/DASH) stream by limiting the bitrate
+    /// the demuxer may pick. Passing `None` restores automatic selection.
+    ///
+    /// The change applies from the next downloaded fragment.
+    pub fn set_quality(&mut self, quality: Option<Quality>) {
+        let mut inner = self.get_mut();
+        let kbps = quality.map_or(0, |quality| quality.bitrate.div_ceil(1000));
+        adaptive::set_connection_speed(&inner.source, kbps);
+        inner.quality = quality;
+    }
+
+    /// Returns the quality pinned with [`Video::set_quality`], if any.
+    pub fn quality(&self) -> Option<Quality> {
+        self.read().quality
+    }
+
+    /// Sets the [`ThumbnailCache`] thumbnails are looked up in before being
+    /// decoded, and stored in after, or `None` to always decode them.
+    pub fn set_thumbnail_cache(&mut self, thumbnail_cache: Option<ThumbnailCache>) {
+        self.get_mut().thumbnail_cache = thumbnail_cache;
+    }
+
+    /// Plans `coarse` evenly spaced thumbnails, which are refined around the
+    /// regions the user is interested in, see [`AdaptiveThumbnails`].
+    pub fn adaptive_thumbnails(&self, coarse: usize, size: iced::Size<u32>) -> AdaptiveThumbnails {
+        AdaptiveThumbnails::new(self, coarse, size)
+    }
+
+    /// Subscribes to the end of stream, errors, buffering and tags of the
+    /// pipeline as they are posted.
+    ///
+    /// Unlike the messages of [`VideoPlayer`](crate::VideoPlayer), which are
+    /// only sent while it is drawn, events also arrive while the window is
+    /// hidden or minimized.
+    pub fn events(&self) -> iced::Subscription<Event> {
+        self.read().events.subscription()
+    }
+
+    /// Captures the position, speed, volume, tracks and filters of the video,
+    /// so that playback can continue later with [`Video::restore_state`].
+    pub fn snapshot_state(&self) -> PlaybackState {
+        let track = |stream_type| {
+            let track = self.read().current_track(stream_type);
+            (track >= 0).then_some(track)
+        };
+        PlaybackState {
+            position: self.position(),
+            paused: self.paused(),
+            speed: self.speed(),
+            volume: self.volume(),
+            muted: self.muted(),
+            audio_track: track(gst::StreamType::AUDIO),
+            text_track: track(gst::StreamType::TEXT),
+            brightness: self.brightness(),
+            contrast: self.contrast(),
+            hue: self.hue(),
+            saturation: self.saturation(),
+            gamma: self.gamma(),
+        }
+    }
+
+    /// Restores a [`PlaybackState`] captured by [`Video::snapshot_state`],
+    /// e.g., after reopening the same media.
+    ///
+    /// Tracks which don't exist in the media are left alone.
+    pub fn restore_state(&mut self, state: &PlaybackState) -> Result<(), Error> {
+        self.set_brightness(state.brightness);
+        self.set_contrast(state.contrast);
+        self.set_hue(state.hue);
+        self.set_saturation(state.saturation);
+        self.set_gamma(state.gamma);
+        self.set_volume(state.volume);
+        self.set_muted(state.muted);
+
+        {
+            let mut inner = self.get_mut();
+            for (stream_type, track) in [
+                (gst::StreamType::AUDIO, state.audio_track),
+                (gst::StreamType::TEXT, state.text_track),
+            ] {
+                if let Some(track) = track
+                    && track < inner.track_count(stream_type)
+                {
+                    inner.select_track(stream_type, track);
+                }
+            }
+        }
+
+        self.seek(state.position, true)?;
+        self.set_speed(state.speed)?;
+        self.set_paused(state.paused);
+        Ok(())
+    }
+
+    /// Get a [`VideoHandle`] which controls the video from other threads,
+    /// without borrowing it.
+    pub fn handle(&self) -> VideoHandle {
+        VideoHandle(Arc::downgrade(&self.0))
+    }
+
     /// Get the underlying GStreamer pipeline.
     pub fn pipeline(&self) -> gst::Pipeline {
         self.read().source.clone()
     }
 
-    /// Generates a list of thumbnails based on a set of positions in the media, downscaled by a given factor.
+    /// Generates a list of thumbnails based on a set of positions in the media, scaled to `size`.
     ///
-    /// Slow; only needs to be called once for each instance.
-    /// It's best to call this at the very start of playback, otherwise the position may shift.
+    /// The size is rounded down to even dimensions. The video is letterboxed
+    /// within it if the aspect ratios differ.
+    ///
+    /// Slow; the media is decoded anew by a separate pipeline, so playback is
+    /// not affected. Fails with [`Error::Uri`] for videos not loaded from a URI
+    /// (e.g., [`Video::from_frames`]).
+    ///
+    /// For long media, see [`Video::adaptive_thumbnails`].
     pub fn thumbnails<I>(
-        &mut self,
+        &self,
         positions: I,
-        downscale: NonZeroU8,
+        size: iced::Size<u32>,
     ) -> Result<Vec<img::Handle>, Error>
     where
         I: IntoIterator<Item = Position>,
     {
-        let downscale = u8::from(downscale) as u32;
+        self.thumbnails_until(positions, size, &AtomicBool::new(false))
+    }
+
+    /// Like [`Video::thumbnails`], but packs the thumbnails into a single
+    /// [`SpriteSheet`] of `columns` thumbnails per row, as used by seek previews.
+    pub fn thumbnail_sprite_sheet<I>(
+        &self,
+        positions: I,
+        size: iced::Size<u32>,
+        columns: NonZeroU32,
+    ) -> Result<SpriteSheet, Error>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let (size, thumbnails) = self.thumbnail_data(positions, size, &AtomicBool::new(false))?;
+        Ok(SpriteSheet::pack(size, columns.get(), &thumbnails))
+    }
 
-        let paused = self.paused();
-        let muted = self.muted();
-        let pos = self.position();
+    /// Like [`Video::thumbnails`], but stops early once `cancel` is set,
+    /// returning the thumbnails generated so far.
+    pub(crate) fn thumbnails_until<I>(
+        &self,
+        positions: I,
+        size: iced::Size<u32>,
+        cancel: &AtomicBool,
+    ) -> Result<Vec<img::Handle>, Error>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        let (size, thumbnails) = self.thumbnail_data(positions, size, cancel)?;
+        Ok(thumbnails
+            .into_iter()
+            .map(|rgba| img::Handle::from_rgba(size.width, size.height, rgba))
+            .collect())
+    }
+
+    /// Decodes the thumbnails as RGBA, returning them with the size they were scaled to.
+    fn thumbnail_data<I>(
+        &self,
+        positions: I,
+        size: iced::Size<u32>,
+        cancel: &AtomicBool,
+    ) -> Result<(iced::Size<u32>, Vec<Vec<u8>>), Error>
+    where
+        I: IntoIterator<Item = Position>,
+    {
+        // NV12 is subsampled in pairs of pixels
+        let size = iced::Size::new(size.width.max(2) & !1, size.height.max(2) & !1);
 
-        self.set_paused(false);
-        self.set_muted(true);
+        // audio-only media has no frames to extract
+        if !self.has_video() {
+            return Ok((size, Vec::new()));
+        }
 
-        let out = {
+        let (uri, framerate, thumbnail_cache) = {
             let inner = self.read();
-            let width = inner.width;
-            let height = inner.height;
-            positions
-                .into_iter()
-                .map(|pos| {
-                    inner.seek(pos, true)?;
-                    inner.upload_frame.store(false, Ordering::SeqCst);
-                    while !inner.upload_frame.load(Ordering::SeqCst) {
-                        std::hint::spin_loop();
-                    }
-                    let frame_guard = inner.frame.lock().map_err(|_| Error::Lock)?;
-                    let frame = frame_guard.readable().ok_or(Error::Lock)?;
-                    let stride = frame_guard.stride();
-
-                    Ok(img::Handle::from_rgba(
-                        inner.width as u32 / downscale,
-                        inner.height as u32 / downscale,
-                        yuv_to_rgba(frame.as_slice(), width as _, height as _, downscale, stride),
-                    ))
+            (
+                inner.uri().ok_or(Error::Uri)?,
+                inner.framerate,
+                inner.thumbnail_cache.clone(),
+            )
+        };
+        // frame seeks aren't supported by every demuxer, unlike time seeks
+        let positions: Vec<_> = positions
+            .into_iter()
+            .map(|position| match position {
+                Position::Time(time) => time,
+                Position::Frame(frame) => Duration::from_secs_f64(frame as f64 / framerate),
+            })
+            .collect();
+
+        let Some(thumbnail_cache) = thumbnail_cache else {
+            let thumbnails = extract_thumbnails(&uri, positions.into_iter(), size, cancel)?;
+            return Ok((size, thumbnails));
+        };
+
+        // only the thumbnails missing from the cache are decoded
+        let media = cache::media_key(&uri);
+        let cached: Vec<_> = positions
+            .iter()
+            .map(|&position| thumbnail_cache.load(&media, position, size))
+            .collect();
+        let missing = positions
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(&position, _)| position);
+        let mut decoded = extract_thumbnails(&uri, missing, size, cancel)?.into_iter();
+
+        // decoding stops early when cancelled, so do the thumbnails returned
+        let thumbnails = positions
+            .into_iter()
+            .zip(cached)
+            .map_while(|(position, cached)| {
+                cached.or_else(|| {
+                    let rgba = decoded.next()?;
+                    thumbnail_cache.store(&media, position, size, &rgba);
+                    Some(rgba)
                 })
-                .collect()
+            })
+            .collect();
+        Ok((size, thumbnails))
+    }
+}
+
+/// Coefficients converting YUV samples of a given colorimetry to RGB.
+///
+/// Shared by the CPU conversion and the shader so that exported frames match
+/// what is rendered on screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Colorimetry {
+    /// Rows producing R, G and B from normalized `(Y, U, V)`.
+    pub matrix: [[f32; 3]; 3],
+    /// Offset and scale normalizing Y to `[0, 1]`.
+    pub y_range: (f32, f32),
+    /// Scale normalizing U and V to `[-0.5, 0.5]` once centered.
+    pub uv_scale: f32,
+}
+
+impl Default for Colorimetry {
+    fn default() -> Self {
+        Colorimetry::new(VideoColorMatrix::Bt709, VideoColorRange::Range16_235)
+    }
+}
+
+impl Colorimetry {
+    pub fn new(matrix: VideoColorMatrix, range: VideoColorRange) -> Self {
+        let (kr, kb) = match matrix {
+            VideoColorMatrix::Bt601 => (0.299, 0.114),
+            VideoColorMatrix::Bt2020 => (0.2627, 0.0593),
+            VideoColorMatrix::Smpte240m => (0.212, 0.087),
+            VideoColorMatrix::Fcc => (0.30, 0.11),
+            _ => (0.2126, 0.0722),
+        };
+        let kg = 1.0 - kr - kb;
+
+        let (y_range, uv_scale) = match range {
+            VideoColorRange::Range0_255 => ((0.0, 1.0), 1.0),
+            _ => ((16.0 / 255.0, 255.0 / 219.0), 255.0 / 224.0),
         };
 
-        self.set_paused(paused);
-        self.set_muted(muted);
-        self.seek(pos, true)?;
+        Colorimetry {
+            matrix: [
+                [1.0, 0.0, 2.0 * (1.0 - kr)],
+                [
+                    1.0,
+                    -2.0 * kb * (1.0 - kb) / kg,
+                    -2.0 * kr * (1.0 - kr) / kg,
+                ],
+                [1.0, 2.0 * (1.0 - kb), 0.0],
+            ],
+            y_range,
+            uv_scale,
+        }
+    }
+
+    /// Converts 8-bit YUV samples to RGBA.
+    pub fn convert(&self, y: u8, u: u8, v: u8) -> [u8; 4] {
+        let yuv = [
+            (y as f32 / 255.0 - self.y_range.0) * self.y_range.1,
+            (u as f32 / 255.0 - 0.5) * self.uv_scale,
+            (v as f32 / 255.0 - 0.5) * self.uv_scale,
+        ];
+        let [r, g, b] = self.matrix.map(|row| {
+            let value = row[0] * yuv[0] + row[1] * yuv[1] + row[2] * yuv[2];
+            (value.clamp(0.0, 1.0) * 255.0).round() as u8
+        });
+        [r, g, b, 0xFF]
+    }
+}
 
-        out
+/// Scales the width of a frame with non-square pixels so its pixels are square.
+fn square_pixels(width: i32, height: i32, pixel_aspect_ratio: gst::Fraction) -> (i32, i32) {
+    if pixel_aspect_ratio.numer() <= 0 || pixel_aspect_ratio.denom() <= 0 {
+        return (width, height);
     }
+    let width =
+        width as i64 * pixel_aspect_ratio.numer() as i64 / pixel_aspect_ratio.denom() as i64;
+    (width as i32, height)
 }
 
 fn yuv_to_rgba(
@@ -936,6 +3857,7 @@ fn yuv_to_rgba(
     height: u32,
     downscale: u32,
     stride: Option<u32>,
+    colorimetry: &Colorimetry,
 ) -> Vec<u8> {
     // Use stride from VideoMeta if available, otherwise assume stride == width
     let stride = stride.unwrap_or(width);
@@ -955,18 +3877,11 @@ fn yuv_to_rgba(
             let y_offset = (y_src * stride + x_src) as usize;
             let uv_offset = (uv_start + (y_src / 2) * stride + (x_src / 2) * 2) as usize;
 
-            let y = yuv[y_offset] as f32;
-            let u = yuv[uv_offset] as f32;
-            let v = yuv[uv_offset + 1] as f32;
-
-            let r = 1.164 * (y - 16.0) + 1.596 * (v - 128.0);
-            let g = 1.164 * (y - 16.0) - 0.813 * (v - 128.0) - 0.391 * (u - 128.0);
-            let b = 1.164 * (y - 16.0) + 2.018 * (u - 128.0);
-
-            rgba.push(r as u8);
-            rgba.push(g as u8);
-            rgba.push(b as u8);
-            rgba.push(0xFF);
+            rgba.extend_from_slice(&colorimetry.convert(
+                yuv[y_offset],
+                yuv[uv_offset],
+                yuv[uv_offset + 1],
+            ));
         }
     }
 
@@ -977,16 +3892,71 @@ fn yuv_to_rgba(
 /// Subtitle meta data.
 pub struct TextTag {
     id: i32,
-    /// The language of the subtitle.
-    pub language_code: String,
-    /// The title of the subtitle.
-    pub title: String,
+    /// The language of the subtitle, if known.
+    pub language_code: Option<String>,
+    /// The title of the subtitle (e.g., "Commentary"), if any.
+    pub title: Option<String>,
+    /// The format of the subtitle (e.g., "SubRip"), if known.
+    pub codec: Option<String>,
+}
+
+impl TextTag {
+    /// Get the index of the subtitle track, which stays the same for the media.
+    pub fn id(&self) -> i32 {
+        self.id
+    }
 }
 
 impl std::fmt::Display for TextTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} - {}", self.title, self.language_code)
+        match (&self.title, &self.language_code) {
+            (Some(title), Some(language)) => write!(f, "{title} - {language}")?,
+            (Some(label), None) | (None, Some(label)) => write!(f, "{label}")?,
+            (None, None) => write!(f, "Track {}", self.id + 1)?,
+        }
+        if let Some(codec) = &self.codec {
+            write!(f, " ({codec})")?;
+        }
+        Ok(())
+    }
+}
+
+/// A chapter of the media, from its table of contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    /// The chapter title, if any.
+    pub title: Option<String>,
+    /// Where the chapter starts.
+    pub start: Duration,
+    /// Where the chapter ends, if known.
+    pub end: Option<Duration>,
+}
+
+/// Collects the chapters of a table of contents, including nested ones, by start time.
+pub(crate) fn toc_chapters(toc: &gst::TocRef) -> Vec<Chapter> {
+    fn collect(entries: &[gst::TocEntry], chapters: &mut Vec<Chapter>) {
+        for entry in entries {
+            if entry.entry_type() == gst::TocEntryType::Chapter
+                && let Some((start, stop)) = entry.start_stop_times()
+                && start >= 0
+            {
+                chapters.push(Chapter {
+                    title: entry
+                        .tags()
+                        .and_then(|tags| tags.get::<gst::tags::Title>())
+                        .map(|title| title.get().to_owned()),
+                    start: Duration::from_nanos(start as u64),
+                    end: u64::try_from(stop).ok().map(Duration::from_nanos),
+                });
+            }
+            collect(&entry.sub_entries(), chapters);
+        }
     }
+
+    let mut chapters = Vec::new();
+    collect(&toc.entries(), &mut chapters);
+    chapters.sort_by_key(|chapter| chapter.start);
+    chapters
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -994,43 +3964,363 @@ impl std::fmt::Display for TextTag {
 pub struct AudioTag {
     /// The audio index
     pub id: i32,
-    /// The audio language.
-    pub language_code: String,
-    /// the audio codec
-    pub codec: String,
-    /// The audio title
-    pub title: String,
+    /// The audio language, if known.
+    pub language_code: Option<String>,
+    /// the audio codec, if known
+    pub codec: Option<String>,
+    /// The audio title, if any.
+    pub title: Option<String>,
+    /// The number of channels (e.g., 6 for 5.1), if known.
+    pub channels: Option<u32>,
+    /// The sample rate in Hz, if known.
+    pub sample_rate: Option<u32>,
+    /// The bitrate in bits per second, if known.
+    pub bitrate: Option<u32>,
 }
 
 impl std::fmt::Display for AudioTag {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} - {}", self.title, self.language_code)
+        match (&self.title, &self.language_code) {
+            (Some(title), Some(language)) => write!(f, "{title} - {language}")?,
+            (Some(label), None) | (None, Some(label)) => write!(f, "{label}")?,
+            (None, None) => write!(f, "Track {}", self.id + 1)?,
+        }
+        let details: Vec<_> = [
+            self.codec.clone(),
+            self.channels.map(|channels| match channels {
+                1 => "mono".to_owned(),
+                2 => "stereo".to_owned(),
+                6 => "5.1".to_owned(),
+                8 => "7.1".to_owned(),
+                channels => format!("{channels} channels"),
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
     }
 }
 
-fn get_audio(pipeline: &gst::Pipeline, id: i32) -> Option<AudioTag> {
+/// Compares ISO 639 codes, ignoring case and regions (e.g., `en-US` matches `en`),
+/// and across ISO 639-1 and 639-2 (e.g., `deu` and `ger` match `de`).
+fn language_matches(language: &str, preferred: &str) -> bool {
+    primary_language(language) == primary_language(preferred)
+}
+
+/// Finds a picture attached to the tags of the current audio stream.
+fn attached_picture(pipeline: &gst::Pipeline) -> Option<gst::Sample> {
+    if !pipeline.has_property("current-audio", None) {
+        return None;
+    }
+    let id = pipeline.property::<i32>("current-audio").max(0);
     let tags = pipeline.emit_by_name::<Option<gst::TagList>>("get-audio-tags", &[&id])?;
+    tags.get::<gst::tags::Image>()
+        .or_else(|| tags.get::<gst::tags::PreviewImage>())
+        .map(|picture| picture.get())
+}
+
+/// Decodes the frames at `positions` as RGBA of `size`, on a pipeline separate from playback.
+fn extract_thumbnails(
+    uri: &str,
+    positions: impl Iterator<Item = Duration>,
+    size: iced::Size<u32>,
+    cancel: &AtomicBool,
+) -> Result<Vec<Vec<u8>>, Error> {
+    // `videoscale` letterboxes frames to keep their aspect ratio
+    let pipeline = gst::parse::launch(&format!(
+        "uridecodebin uri=\"{uri}\" ! videoconvert ! videoscale ! appsink name=iced_thumbnails sync=false caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1,width={},height={}",
+        size.width, size.height
+    ))?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| Error::Cast("gst::Pipeline"))?;
+    let sink = pipeline
+        .by_name("iced_thumbnails")
+        .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+        .ok_or_else(|| Error::AppSink("iced_thumbnails".into()))?;
+
+    let thumbnail = |position: Duration| -> Result<Vec<u8>, Error> {
+        pipeline.seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+            gst::ClockTime::from_nseconds(position.as_nanos() as _),
+        )?;
+        // the sink prerolls on the frame at the position once the seek completes
+        let frame = Frame(sink.pull_preroll().map_err(|_| {
+            pipeline
+                .bus()
+                .and_then(|bus| PipelineError::from_bus(&bus))
+                .map_or(Error::Sync, Error::from)
+        })?);
+        let (width, height) = frame.size().ok_or(Error::Sync)?;
+        let map = frame.readable().ok_or(Error::Lock)?;
+        Ok(yuv_to_rgba(
+            map.as_slice(),
+            width,
+            height,
+            1,
+            frame.stride(),
+            &frame.colorimetry(),
+        ))
+    };
+
+    pipeline.set_state(gst::State::Paused)?;
+    let out = pipeline
+        .state(gst::ClockTime::from_seconds(5))
+        .0
+        .map_err(Error::from)
+        .and_then(|_| {
+            positions
+                .take_while(|_| !cancel.load(Ordering::SeqCst))
+                .map(thumbnail)
+                .collect()
+        });
+    pipeline.set_state(gst::State::Null)?;
+    out
+}
+
+/// Decodes an attached picture into an NV12 sample, like the frames of the video sink.
+fn decode_picture(picture: &gst::Sample) -> Result<gst::Sample, Error> {
+    let pipeline = gst::parse::launch(
+        "appsrc name=iced_picture ! decodebin ! videoconvert ! videoscale ! appsink name=iced_picture_sink caps=video/x-raw,format=NV12,pixel-aspect-ratio=1/1",
+    )?
+    .downcast::<gst::Pipeline>()
+    .map_err(|_| Error::Cast("gst::Pipeline"))?;
+    let src = pipeline
+        .by_name("iced_picture")
+        .and_then(|src| src.downcast::<gst_app::AppSrc>().ok())
+        .ok_or(Error::Cast("gst_app::AppSrc"))?;
+    let sink = pipeline
+        .by_name("iced_picture_sink")
+        .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+        .ok_or_else(|| Error::AppSink("iced_picture_sink".into()))?;
+    let buffer = picture
+        .buffer_owned()
+        .ok_or_else(|| Error::Caps("attached picture has no data".into()))?;
+
+    src.set_caps(picture.caps());
+    pipeline.set_state(gst::State::Playing)?;
+    // failures surface below, as the sink never receives a sample
+    let _ = src.push_buffer(buffer);
+    let _ = src.end_of_stream();
+
+    let sample = sink.try_pull_sample(gst::ClockTime::from_seconds(5));
+    let error = pipeline.bus().and_then(|bus| PipelineError::from_bus(&bus));
+    pipeline.set_state(gst::State::Null)?;
+    match (sample, error) {
+        (Some(sample), _) => Ok(sample),
+        (None, Some(error)) => Err(error.into()),
+        (None, None) => Err(Error::Caps("attached picture could not be decoded".into())),
+    }
+}
+
+/// Sets or unsets one of playbin's `flags` by its nick.
+pub(crate) fn set_flag(pipeline: &gst::Pipeline, nick: &str, enabled: bool) {
+    let flags = pipeline.property_value("flags");
+    let flags_class =
+        FlagsClass::with_type(flags.type_()).expect("Playbin pipeline should have flags");
 
-    let language = tags.get::<gst::tags::LanguageCode>()?;
-    let codec = tags.get::<gst::tags::AudioCodec>()?;
-    let title = tags.get::<gst::tags::Title>()?;
+    let builder = flags_class.builder_with_value(flags).unwrap();
 
-    Some(AudioTag {
-        id,
-        language_code: language.get().to_owned(),
-        codec: codec.get().to_owned(),
-        title: title.get().to_owned(),
-    })
+    let flags = if enabled {
+        builder.set_by_nick(nick)
+    } else {
+        builder.unset_by_nick(nick)
+    }
+    .build()
+    .unwrap();
+
+    pipeline.set_property_from_value("flags", &flags);
+}
+
+/// Guesses whether a subtitle track is meant for the deaf and hard of hearing from its title.
+fn is_sdh(title: &str) -> bool {
+    let title = title.to_lowercase();
+    title.contains("hearing impaired")
+        || title
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word == "sdh" || word == "cc")
 }
 
-fn get_text(pipeline: &gst::Pipeline, id: i32) -> Option<TextTag> {
-    let tags = pipeline.emit_by_name::<Option<gst::TagList>>("get-text-tags", &[&id])?;
-    let codec = tags.get::<gst::tags::LanguageCode>()?;
-    let title = tags.get::<gst::tags::Title>()?;
+/// Get the ids of the streams selected by `playbin3`.
+pub(crate) fn stream_ids(selected: &gst::message::StreamsSelected) -> Vec<String> {
+    selected
+        .streams()
+        .filter_map(|stream| stream.stream_id())
+        .map(|id| id.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{a} != {b}");
+    }
+
+    #[test]
+    fn selects_matrix_coefficients() {
+        let bt709 = Colorimetry::new(VideoColorMatrix::Bt709, VideoColorRange::Range16_235);
+        assert_close(bt709.matrix[0][2], 2.0 * (1.0 - 0.2126));
+        assert_close(bt709.matrix[2][1], 2.0 * (1.0 - 0.0722));
+
+        let bt601 = Colorimetry::new(VideoColorMatrix::Bt601, VideoColorRange::Range16_235);
+        assert_close(bt601.matrix[0][2], 1.402);
+        assert_close(bt601.matrix[2][1], 1.772);
+
+        let bt2020 = Colorimetry::new(VideoColorMatrix::Bt2020, VideoColorRange::Range16_235);
+        assert_close(bt2020.matrix[0][2], 2.0 * (1.0 - 0.2627));
+
+        // unknown matrices are treated as BT.709, as is the default
+        let unknown = Colorimetry::new(VideoColorMatrix::Unknown, VideoColorRange::Range16_235);
+        assert_eq!(unknown, bt709);
+        assert_eq!(Colorimetry::default(), bt709);
+    }
+
+    #[test]
+    fn selects_range() {
+        let limited = Colorimetry::new(VideoColorMatrix::Bt709, VideoColorRange::Range16_235);
+        assert_close(limited.y_range.0, 16.0 / 255.0);
+        assert_close(limited.y_range.1, 255.0 / 219.0);
+        assert_close(limited.uv_scale, 255.0 / 224.0);
+
+        let full = Colorimetry::new(VideoColorMatrix::Bt709, VideoColorRange::Range0_255);
+        assert_eq!(full.y_range, (0.0, 1.0));
+        assert_eq!(full.uv_scale, 1.0);
+
+        // an unknown range is assumed to be limited
+        let unknown = Colorimetry::new(VideoColorMatrix::Bt709, VideoColorRange::Unknown);
+        assert_eq!(unknown, limited);
+    }
+
+    /// Compares colors, allowing for the rounding of 8-bit samples.
+    fn assert_rgb(actual: [u8; 4], expected: [u8; 3]) {
+        let close = actual[..3]
+            .iter()
+            .zip(expected)
+            .all(|(actual, expected)| actual.abs_diff(expected) <= 2);
+        assert!(close && actual[3] == 0xFF, "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn converts_black_and_white() {
+        let limited = Colorimetry::new(VideoColorMatrix::Bt709, VideoColorRange::Range16_235);
+        assert_rgb(limited.convert(16, 128, 128), [0, 0, 0]);
+        assert_rgb(limited.convert(235, 128, 128), [255, 255, 255]);
+        // values outside the limited range are clamped
+        assert_rgb(limited.convert(0, 128, 128), [0, 0, 0]);
+        assert_rgb(limited.convert(255, 128, 128), [255, 255, 255]);
+
+        let full = Colorimetry::new(VideoColorMatrix::Bt601, VideoColorRange::Range0_255);
+        assert_rgb(full.convert(0, 128, 128), [0, 0, 0]);
+        assert_rgb(full.convert(255, 128, 128), [255, 255, 255]);
+    }
+
+    #[test]
+    fn converts_primaries() {
+        // BT.709 limited range red, green and blue
+        let bt709 = Colorimetry::default();
+        assert_rgb(bt709.convert(63, 102, 240), [255, 0, 0]);
+        assert_rgb(bt709.convert(173, 42, 26), [0, 255, 0]);
+        assert_rgb(bt709.convert(32, 240, 118), [0, 0, 255]);
+    }
+
+    #[test]
+    fn fades_volume_between_endpoints() {
+        let start = Instant::now();
+        let fade = VolumeFade {
+            from: 1.0,
+            to: 0.0,
+            start,
+            duration: Duration::from_secs(2),
+        };
+        assert_eq!(fade.volume_at(start), (1.0, false));
+        assert_eq!(fade.volume_at(start + Duration::from_secs(1)), (0.5, false));
+        assert_eq!(fade.volume_at(start + Duration::from_secs(2)), (0.0, true));
+        // the volume holds at the target once the fade completed
+        assert_eq!(fade.volume_at(start + Duration::from_secs(5)), (0.0, true));
+    }
+
+    #[test]
+    fn fades_volume_instantly() {
+        let start = Instant::now();
+        let fade = VolumeFade {
+            from: 0.25,
+            to: 0.75,
+            start,
+            duration: Duration::ZERO,
+        };
+        assert_eq!(fade.volume_at(start), (0.75, true));
+    }
+
+    #[test]
+    fn replaces_fade_mid_way() {
+        let start = Instant::now();
+        let fade_out = VolumeFade {
+            from: 1.0,
+            to: 0.0,
+            start,
+            duration: Duration::from_secs(4),
+        };
+        // interrupted after a quarter, the new fade starts from where the old one was
+        let interrupted = start + Duration::from_secs(1);
+        let (volume, done) = fade_out.volume_at(interrupted);
+        assert_eq!((volume, done), (0.75, false));
+
+        let fade_in = VolumeFade {
+            from: volume,
+            to: 1.0,
+            start: interrupted,
+            duration: Duration::from_secs(1),
+        };
+        assert_eq!(fade_in.volume_at(interrupted), (0.75, false));
+        assert_eq!(
+            fade_in.volume_at(interrupted + Duration::from_millis(500)),
+            (0.875, false)
+        );
+        assert_eq!(
+            fade_in.volume_at(interrupted + Duration::from_secs(1)),
+            (1.0, true)
+        );
+    }
+
+    #[test]
+    fn matches_languages() {
+        assert!(language_matches("en", "en"));
+        assert!(language_matches("EN", "en"));
+        assert!(language_matches("en-US", "en"));
+        assert!(language_matches("pt_BR", "pt-PT"));
+        assert!(!language_matches("en", "de"));
+        assert!(!language_matches("", "en"));
+    }
+
+    #[test]
+    fn matches_iso_639_2_languages() {
+        assert!(language_matches("eng", "en"));
+        assert!(language_matches("en", "eng"));
+        assert!(language_matches("deu", "de"));
+        assert!(language_matches("ger", "de"));
+        assert!(language_matches("ger", "deu"));
+        assert!(language_matches("fre", "fr"));
+        assert!(language_matches("zho", "chi"));
+        assert!(!language_matches("deu", "en"));
+        // codes without an ISO 639-1 equivalent only match themselves
+        assert!(language_matches("yue", "yue"));
+        assert!(!language_matches("yue", "zh"));
+    }
 
-    Some(TextTag {
-        id,
-        language_code: codec.get().to_owned(),
-        title: title.get().to_owned(),
-    })
+    #[test]
+    fn detects_sdh() {
+        assert!(is_sdh("SDH"));
+        assert!(is_sdh("English (SDH)"));
+        assert!(is_sdh("English [CC]"));
+        assert!(is_sdh("For the Hearing Impaired"));
+        assert!(!is_sdh(""));
+        assert!(!is_sdh("English"));
+        assert!(!is_sdh("Commentary"));
+        assert!(!is_sdh("Forced"));
+    }
 }
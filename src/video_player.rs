@@ -1,5 +1,12 @@
-use crate::{pipeline::VideoPrimitive, video::Video};
+use crate::pipeline::{InsetPrimitive, VideoPrimitive};
+use crate::{
+    AudioLevel, MissingPlugin, PipelineError, StreamHealth, StyledSubtitle, SubtitleCue, health,
+    level::Silence,
+    pipeline::{CustomShader, DynamicRange},
+    video::{FrameInfo, Internal, Orientation, PlaybackStatus, Video},
+};
 use gstreamer as gst;
+use gstreamer::prelude::*;
 pub use iced::advanced::mouse::{Button, ScrollDelta, click::Kind};
 #[allow(unused_imports)]
 pub use iced::keyboard::{Key, Modifiers, key};
@@ -9,9 +16,10 @@ use iced::{
         self, Widget, layout, mouse,
         widget::{self, tree},
     },
-    keyboard, window,
+    keyboard, touch,
+    widget::image as img,
+    window,
 };
-use iced_wgpu::primitive::Renderer as PrimitiveRenderer;
 use log::error;
 use std::{f32, marker::PhantomData, sync::atomic::Ordering};
 use std::{
@@ -19,41 +27,185 @@ use std::{
     time::{Duration, Instant},
 };
 
+/// A renderer able to draw a [`VideoPlayer`].
+///
+/// The wgpu renderer draws frames as primitives, which apply the [`Effect`](crate::Effect),
+/// [`CubeLut`](crate::CubeLut) and [`CustomShader`] of the video on the GPU.
+/// The tiny-skia renderer draws frames as images converted on the CPU, without
+/// effects, LUTs, custom shaders or flipped orientations. iced's default
+/// renderer uses whichever of the two it fell back to.
+///
+/// Images (e.g., an [`EosBehavior::Poster`]) are drawn as usual.
+pub trait VideoRenderer: advanced::image::Renderer<Handle = img::Handle> {
+    /// Get if frames are drawn as wgpu primitives rather than images.
+    #[doc(hidden)]
+    fn supports_primitives(&self) -> bool;
+
+    /// Draws a frame as a wgpu primitive, see [`VideoRenderer::supports_primitives`].
+    #[doc(hidden)]
+    fn draw_video_primitive(
+        &mut self,
+        bounds: iced::Rectangle,
+        primitive: impl iced_wgpu::primitive::Primitive,
+    );
+}
+
+impl VideoRenderer for iced_wgpu::Renderer {
+    fn supports_primitives(&self) -> bool {
+        true
+    }
+
+    fn draw_video_primitive(
+        &mut self,
+        bounds: iced::Rectangle,
+        primitive: impl iced_wgpu::primitive::Primitive,
+    ) {
+        use iced_wgpu::primitive::Renderer as _;
+        self.draw_primitive(bounds, primitive);
+    }
+}
+
+impl VideoRenderer for iced_tiny_skia::Renderer {
+    fn supports_primitives(&self) -> bool {
+        false
+    }
+
+    fn draw_video_primitive(
+        &mut self,
+        _bounds: iced::Rectangle,
+        _primitive: impl iced_wgpu::primitive::Primitive,
+    ) {
+    }
+}
+
+impl<A: VideoRenderer, B: VideoRenderer> VideoRenderer for iced_renderer::fallback::Renderer<A, B> {
+    fn supports_primitives(&self) -> bool {
+        match self {
+            Self::Primary(renderer) => renderer.supports_primitives(),
+            Self::Secondary(renderer) => renderer.supports_primitives(),
+        }
+    }
+
+    fn draw_video_primitive(
+        &mut self,
+        bounds: iced::Rectangle,
+        primitive: impl iced_wgpu::primitive::Primitive,
+    ) {
+        match self {
+            Self::Primary(renderer) => renderer.draw_video_primitive(bounds, primitive),
+            Self::Secondary(renderer) => renderer.draw_video_primitive(bounds, primitive),
+        }
+    }
+}
+
 /// Video player widget which displays the current frame of a [`Video`](crate::Video).
+///
+/// The same video may be shown by several players at once (e.g., a main view
+/// and a filmstrip). Events of the pipeline, such as the end of the stream, are
+/// only reported by one of them, and only while it is drawn; see [`Video::events`]
+/// to receive them regardless.
 pub struct VideoPlayer<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer>
 where
-    Renderer: PrimitiveRenderer,
+    Renderer: VideoRenderer,
 {
     video: &'a Video,
     content_fit: iced::ContentFit,
+    background: Option<iced::Background>,
+    opacity: f32,
+    fade_in: Option<Duration>,
+    shader: Option<CustomShader>,
+    inset: Option<(&'a Video, Inset)>,
     width: iced::Length,
     height: iced::Length,
     on_end_of_stream: Option<Message>,
     on_new_frame: Option<Message>,
+    on_frame_info: Option<Box<dyn Fn(FrameInfo) -> Message + 'a>>,
+    on_frames_dropped: Option<Box<dyn Fn(u64) -> Message + 'a>>,
+    on_position: Option<(Duration, Box<dyn Fn(Duration) -> Message + 'a>)>,
+    on_stream_health: Option<(Duration, Box<dyn Fn(StreamHealth) -> Message + 'a>)>,
+    on_audio_level: Option<Box<dyn Fn(AudioLevel) -> Message + 'a>>,
+    on_silence_start: Option<Box<dyn Fn(Duration) -> Message + 'a>>,
+    on_silence_end: Option<Box<dyn Fn(Duration) -> Message + 'a>>,
+    on_scene_change: Option<Box<dyn Fn(Duration) -> Message + 'a>>,
+    on_state_changed: Option<Box<dyn Fn(PlaybackStatus) -> Message + 'a>>,
+    on_milestone: Option<(Vec<f64>, Box<dyn Fn(f64) -> Message + 'a>)>,
+    on_clock_lost: Option<Message>,
+    on_latency: Option<Message>,
+    on_output_range_changed: Option<Box<dyn Fn(DynamicRange) -> Message + 'a>>,
+    on_resolution_changed: Option<Box<dyn Fn((i32, i32)) -> Message + 'a>>,
+    on_duration_changed: Option<Box<dyn Fn(Duration) -> Message + 'a>>,
+    on_visible_region_changed: Option<Box<dyn Fn(VisibleRegion) -> Message + 'a>>,
+    on_approval_required: Option<Message>,
     on_subtitle_text: Option<Box<dyn Fn(Option<String>) -> Message + 'a>>,
-    on_error: Option<Box<dyn Fn(&glib::Error) -> Message + 'a>>,
+    on_styled_subtitle: Option<Box<dyn Fn(Option<StyledSubtitle>) -> Message + 'a>>,
+    on_subtitle_cue: Option<Box<dyn Fn(Option<SubtitleCue>) -> Message + 'a>>,
+    on_error: Option<Box<dyn Fn(&PipelineError) -> Message + 'a>>,
+    on_missing_plugin: Option<Box<dyn Fn(&MissingPlugin) -> Message + 'a>>,
     on_keypress: Option<Box<dyn Fn(KeyPress) -> Option<Message> + 'a>>,
     on_click: Option<Box<dyn Fn(MouseClick) -> Option<Message> + 'a>>,
+    on_double_click: Option<Message>,
+    click_delay: Option<Duration>,
+    buffering_indicator: bool,
+    wheel_behavior: WheelBehavior,
+    idle_timeout: Duration,
+    eos_behavior: EosBehavior,
+    poster: Option<img::Handle>,
+    drag_seek: bool,
+    touch_gestures: bool,
     _phantom: PhantomData<(Theme, Renderer)>,
 }
 
 impl<'a, Message, Theme, Renderer> VideoPlayer<'a, Message, Theme, Renderer>
 where
-    Renderer: PrimitiveRenderer,
+    Renderer: VideoRenderer,
 {
     /// Creates a new video player widget for a given video.
     pub fn new(video: &'a Video) -> Self {
         VideoPlayer {
             video,
             content_fit: iced::ContentFit::default(),
+            background: None,
+            opacity: 1.0,
+            fade_in: None,
+            shader: None,
+            inset: None,
             width: iced::Length::Shrink,
             height: iced::Length::Shrink,
             on_end_of_stream: None,
             on_new_frame: None,
+            on_frame_info: None,
+            on_frames_dropped: None,
+            on_position: None,
+            on_stream_health: None,
+            on_audio_level: None,
+            on_silence_start: None,
+            on_silence_end: None,
+            on_scene_change: None,
+            on_state_changed: None,
+            on_milestone: None,
+            on_clock_lost: None,
+            on_latency: None,
+            on_output_range_changed: None,
+            on_resolution_changed: None,
+            on_duration_changed: None,
+            on_visible_region_changed: None,
+            on_approval_required: None,
             on_subtitle_text: None,
+            on_styled_subtitle: None,
+            on_subtitle_cue: None,
             on_error: None,
+            on_missing_plugin: None,
             on_keypress: None,
             on_click: None,
+            on_double_click: None,
+            click_delay: None,
+            buffering_indicator: true,
+            wheel_behavior: WheelBehavior::None,
+            idle_timeout: Duration::from_secs(3),
+            eos_behavior: EosBehavior::HoldLastFrame,
+            poster: None,
+            drag_seek: false,
+            touch_gestures: false,
             _phantom: Default::default(),
         }
     }
@@ -82,6 +234,51 @@ where
         }
     }
 
+    /// Sets the `Background` (e.g., a `Color` or `Gradient`) filling the bounds
+    /// of the `VideoPlayer` around the video, such as letterboxing bars.
+    pub fn background(self, background: impl Into<iced::Background>) -> Self {
+        VideoPlayer {
+            background: Some(background.into()),
+            ..self
+        }
+    }
+
+    /// Sets the opacity of the video, from `0.0` (transparent) to `1.0` (opaque),
+    /// e.g., to crossfade between two players.
+    pub fn opacity(self, opacity: f32) -> Self {
+        VideoPlayer { opacity, ..self }
+    }
+
+    /// Fades the video in from black over `duration` once its first frame arrives,
+    /// hiding the frame shown while the decoder is still settling.
+    pub fn fade_in(self, duration: Duration) -> Self {
+        VideoPlayer {
+            fade_in: Some(duration),
+            ..self
+        }
+    }
+
+    /// Runs a [`CustomShader`] on every pixel of the video, e.g., for vignettes
+    /// or analysis overlays.
+    pub fn shader(self, shader: CustomShader) -> Self {
+        VideoPlayer {
+            shader: Some(shader),
+            ..self
+        }
+    }
+
+    /// Composites a secondary video inset within this player (e.g., a camera
+    /// over a screen recording).
+    ///
+    /// Both videos are drawn as a single primitive, which is cheaper and keeps
+    /// them better in sync than stacking two `VideoPlayer`s.
+    pub fn picture_in_picture(self, video: &'a Video, inset: Inset) -> Self {
+        VideoPlayer {
+            inset: Some((video, inset)),
+            ..self
+        }
+    }
+
     /// Message to send when the video reaches the end of stream (i.e., the video ends).
     pub fn on_end_of_stream(self, on_end_of_stream: Message) -> Self {
         VideoPlayer {
@@ -98,6 +295,209 @@ where
         }
     }
 
+    /// Message to send with the [`FrameInfo`] of each new frame, e.g., for
+    /// frame-accurate annotations.
+    ///
+    /// Unlike querying [`Video::position`], the timing describes the frame
+    /// which is displayed.
+    pub fn on_frame_info<F>(self, on_frame_info: F) -> Self
+    where
+        F: 'a + Fn(FrameInfo) -> Message,
+    {
+        VideoPlayer {
+            on_frame_info: Some(Box::new(on_frame_info)),
+            ..self
+        }
+    }
+
+    /// Message to send with the playback position at most once every `interval`
+    /// while the video is playing.
+    ///
+    /// Prefer this over querying [`Video::position`] on every new frame.
+    pub fn on_position<F>(self, interval: Duration, on_position: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        VideoPlayer {
+            on_position: Some((interval, Box::new(on_position))),
+            ..self
+        }
+    }
+
+    /// Message to send with the [`StreamHealth`] of a packet-based source (e.g.,
+    /// an RTSP camera) every `interval`, to warn about degrading links.
+    ///
+    /// Never sent for other sources.
+    pub fn on_stream_health<F>(self, interval: Duration, on_stream_health: F) -> Self
+    where
+        F: 'a + Fn(StreamHealth) -> Message,
+    {
+        VideoPlayer {
+            on_stream_health: Some((interval, Box::new(on_stream_health))),
+            ..self
+        }
+    }
+
+    /// Message to send with the [`AudioLevel`] of each channel while the audio plays.
+    ///
+    /// Levels are only measured if enabled with [`VideoBuilder::audio_levels`](crate::VideoBuilder::audio_levels),
+    /// which also sets how often they are sent.
+    pub fn on_audio_level<F>(self, on_audio_level: F) -> Self
+    where
+        F: 'a + Fn(AudioLevel) -> Message,
+    {
+        VideoPlayer {
+            on_audio_level: Some(Box::new(on_audio_level)),
+            ..self
+        }
+    }
+
+    /// Message to send with the position at which silence started, e.g., to
+    /// offer skipping a quiet intro.
+    ///
+    /// Silence is only detected if enabled with [`VideoBuilder::silence_detection`](crate::VideoBuilder::silence_detection).
+    pub fn on_silence_start<F>(self, on_silence_start: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        VideoPlayer {
+            on_silence_start: Some(Box::new(on_silence_start)),
+            ..self
+        }
+    }
+
+    /// Message to send with the position at which silence ended, see [`VideoPlayer::on_silence_start`].
+    pub fn on_silence_end<F>(self, on_silence_end: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        VideoPlayer {
+            on_silence_end: Some(Box::new(on_silence_end)),
+            ..self
+        }
+    }
+
+    /// Message to send with the position of each scene change, e.g., to place
+    /// chapter markers.
+    ///
+    /// Scene changes are only detected if enabled with [`Video::set_scene_detection`].
+    pub fn on_scene_change<F>(self, on_scene_change: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        VideoPlayer {
+            on_scene_change: Some(Box::new(on_scene_change)),
+            ..self
+        }
+    }
+
+    /// Message to send when the [`PlaybackStatus`] of the video changes, e.g.,
+    /// to keep a play/pause button in sync.
+    pub fn on_state_changed<F>(self, on_state_changed: F) -> Self
+    where
+        F: 'a + Fn(PlaybackStatus) -> Message,
+    {
+        VideoPlayer {
+            on_state_changed: Some(Box::new(on_state_changed)),
+            ..self
+        }
+    }
+
+    /// Message to send once playback first reaches each of the given fractions
+    /// of the duration (e.g., `&[0.25, 0.5, 0.75, 1.0]`), for analytics or to
+    /// mark media as watched. Milestones are reset when the video loops.
+    ///
+    /// A milestone of `1.0` is reached at the end of the stream. Live sources
+    /// never reach any milestone.
+    pub fn on_milestone<F>(self, milestones: &[f64], on_milestone: F) -> Self
+    where
+        F: 'a + Fn(f64) -> Message,
+    {
+        VideoPlayer {
+            on_milestone: Some((milestones.to_vec(), Box::new(on_milestone))),
+            ..self
+        }
+    }
+
+    /// Message to send when the pipeline loses its clock. The pipeline is
+    /// restarted automatically so that a new clock is selected.
+    ///
+    /// This mostly happens with live sources.
+    pub fn on_clock_lost(self, on_clock_lost: Message) -> Self {
+        VideoPlayer {
+            on_clock_lost: Some(on_clock_lost),
+            ..self
+        }
+    }
+
+    /// Message to send when the latency of the pipeline changes. The latency
+    /// is recalculated automatically.
+    ///
+    /// This mostly happens with live sources.
+    pub fn on_latency(self, on_latency: Message) -> Self {
+        VideoPlayer {
+            on_latency: Some(on_latency),
+            ..self
+        }
+    }
+
+    /// Message to send when the [`DynamicRange`] of the surface the video is
+    /// rendered to changes, e.g., when the window moves to an HDR monitor.
+    pub fn on_output_range_changed<F>(self, on_output_range_changed: F) -> Self
+    where
+        F: 'a + Fn(DynamicRange) -> Message,
+    {
+        VideoPlayer {
+            on_output_range_changed: Some(Box::new(on_output_range_changed)),
+            ..self
+        }
+    }
+
+    /// Message to send with the new size of the video when the source changes
+    /// its resolution mid-stream (e.g., adaptive streams switching quality).
+    pub fn on_resolution_changed<F>(self, on_resolution_changed: F) -> Self
+    where
+        F: 'a + Fn((i32, i32)) -> Message,
+    {
+        VideoPlayer {
+            on_resolution_changed: Some(Box::new(on_resolution_changed)),
+            ..self
+        }
+    }
+
+    /// Message to send with the new duration of the media when it changes, e.g.,
+    /// for files which are still being written (like recordings in progress).
+    pub fn on_duration_changed<F>(self, on_duration_changed: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        VideoPlayer {
+            on_duration_changed: Some(Box::new(on_duration_changed)),
+            ..self
+        }
+    }
+
+    /// Message to send with the [`VisibleRegion`] of the video when it changes,
+    /// e.g., to align overlays with the frame when the player is resized.
+    pub fn on_visible_region_changed<F>(self, on_visible_region_changed: F) -> Self
+    where
+        F: 'a + Fn(VisibleRegion) -> Message,
+    {
+        VideoPlayer {
+            on_visible_region_changed: Some(Box::new(on_visible_region_changed)),
+            ..self
+        }
+    }
+
+    /// Message to send when playback gets blocked until approved, see
+    /// [`Video::add_gate`](crate::Video::add_gate).
+    pub fn on_approval_required(self, on_approval_required: Message) -> Self {
+        VideoPlayer {
+            on_approval_required: Some(on_approval_required),
+            ..self
+        }
+    }
+
     /// Message to send when the video receives a new frame.
     pub fn on_subtitle_text<F>(self, on_subtitle_text: F) -> Self
     where
@@ -110,10 +510,41 @@ where
         }
     }
 
+    /// Message to send when the subtitle text changes, keeping the styling and
+    /// positioning of ASS/SSA subtitles, see [`StyledSubtitle::to_spans`].
+    ///
+    /// Other subtitle formats are delivered as a single unstyled span.
+    /// [`VideoPlayer::on_subtitle_text`] receives ASS/SSA subtitles without
+    /// their override tags.
+    pub fn on_styled_subtitle<F>(self, on_styled_subtitle: F) -> Self
+    where
+        F: 'a + Fn(Option<StyledSubtitle>) -> Message,
+    {
+        VideoPlayer {
+            on_styled_subtitle: Some(Box::new(on_styled_subtitle)),
+            ..self
+        }
+    }
+
+    /// Message to send when the subtitle changes, with the timing of the new
+    /// [`SubtitleCue`] (e.g., to fade it out or copy it with its timestamp).
+    pub fn on_subtitle_cue<F>(self, on_subtitle_cue: F) -> Self
+    where
+        F: 'a + Fn(Option<SubtitleCue>) -> Message,
+    {
+        VideoPlayer {
+            on_subtitle_cue: Some(Box::new(on_subtitle_cue)),
+            ..self
+        }
+    }
+
     /// Message to send when the video playback encounters an error.
+    ///
+    /// The [`PipelineError`] carries the element which failed, the category
+    /// of the failure, and GStreamer's debug information.
     pub fn on_error<F>(self, on_error: F) -> Self
     where
-        F: 'a + Fn(&glib::Error) -> Message,
+        F: 'a + Fn(&PipelineError) -> Message,
     {
         VideoPlayer {
             on_error: Some(Box::new(on_error)),
@@ -121,6 +552,127 @@ where
         }
     }
 
+    /// Message to send with the number of frames dropped for being late since
+    /// the last redraw, as reported by Quality of Service (QoS) messages.
+    pub fn on_frames_dropped<F>(self, on_frames_dropped: F) -> Self
+    where
+        F: 'a + Fn(u64) -> Message,
+    {
+        VideoPlayer {
+            on_frames_dropped: Some(Box::new(on_frames_dropped)),
+            ..self
+        }
+    }
+
+    /// Message to send when a GStreamer plugin needed to play the media is
+    /// missing (e.g., an H.265 or AAC decoder). An error usually follows.
+    ///
+    /// Only plugins found missing during playback are reported here; when the
+    /// media can't be opened at all, [`Video::new`] fails with
+    /// [`Error::MissingPlugin`](crate::Error::MissingPlugin) instead.
+    ///
+    /// See [`MissingPlugin::install`] to offer installing it.
+    pub fn on_missing_plugin<F>(self, on_missing_plugin: F) -> Self
+    where
+        F: 'a + Fn(&MissingPlugin) -> Message,
+    {
+        VideoPlayer {
+            on_missing_plugin: Some(Box::new(on_missing_plugin)),
+            ..self
+        }
+    }
+
+    /// Message to send when the video is double clicked with the left mouse
+    /// button, e.g., to toggle fullscreen.
+    ///
+    /// Single left clicks are then deferred until they can't become a double
+    /// click, so that e.g. a pause toggle bound to them doesn't also fire, see
+    /// [`VideoPlayer::click_delay`].
+    pub fn on_double_click(self, on_double_click: Message) -> Self {
+        VideoPlayer {
+            on_double_click: Some(on_double_click),
+            ..self
+        }
+    }
+
+    /// Sets if an animated spinner is drawn over the video while it is
+    /// buffering or stalled. Enabled by default.
+    pub fn buffering_indicator(self, buffering_indicator: bool) -> Self {
+        VideoPlayer {
+            buffering_indicator,
+            ..self
+        }
+    }
+
+    /// Sets what scrolling the mouse wheel over the video does. Scrolls handled
+    /// here are not passed on to [`VideoPlayer::on_click`].
+    pub fn wheel_behavior(self, wheel_behavior: WheelBehavior) -> Self {
+        VideoPlayer {
+            wheel_behavior,
+            ..self
+        }
+    }
+
+    /// Sets how long the cursor stays visible over the video without being
+    /// moved before it is hidden. Defaults to 3 seconds.
+    pub fn idle_timeout(self, idle_timeout: Duration) -> Self {
+        VideoPlayer {
+            idle_timeout,
+            ..self
+        }
+    }
+
+    /// Sets what is shown once the media ended, unless it loops. Holds the last
+    /// frame by default.
+    pub fn eos_behavior(self, eos_behavior: EosBehavior) -> Self {
+        VideoPlayer {
+            eos_behavior,
+            ..self
+        }
+    }
+
+    /// Sets an image shown until the first frame is decoded (e.g., while a
+    /// network source prerolls), and while the video is suspended, see
+    /// [`Video::suspend`](crate::Video::suspend).
+    pub fn poster(self, poster: impl Into<img::Handle>) -> Self {
+        VideoPlayer {
+            poster: Some(poster.into()),
+            ..self
+        }
+    }
+
+    /// Sets if dragging horizontally across the video scrubs through it, with
+    /// the full width of the player spanning the whole duration. The frame and
+    /// a progress bar are updated while dragging, and an accurate seek is made
+    /// on release, see [`Video::scrub`].
+    pub fn drag_seek(self, drag_seek: bool) -> Self {
+        VideoPlayer { drag_seek, ..self }
+    }
+
+    /// Sets if the player reacts to touch gestures:
+    /// - a tap is passed on to [`VideoPlayer::on_click`] as a left click,
+    /// - a double tap on the left or right half seeks 10 seconds backward or forward,
+    /// - a long press plays at double speed until released.
+    pub fn touch_gestures(self, touch_gestures: bool) -> Self {
+        VideoPlayer {
+            touch_gestures,
+            ..self
+        }
+    }
+
+    /// Defers single clicks by `delay`, and drops them if a double click
+    /// follows within it. This allows "single click to pause, double click for
+    /// fullscreen" interfaces, where the single click would otherwise fire first.
+    ///
+    /// The delay should cover the double click interval used by iced (300 ms),
+    /// which is the default once [`VideoPlayer::on_double_click`] is set.
+    pub fn click_delay(self, delay: Duration) -> Self {
+        VideoPlayer {
+            click_delay: Some(delay),
+            ..self
+        }
+    }
+
     /// Sets the message produced when a [`KeyPress`] is received.
     pub fn on_keypress<F>(self, on_keypress: F) -> Self
     where
@@ -132,14 +684,223 @@ where
         }
     }
 
-    /// Sets the message produced when a [`MouseClick`] is received.
-    pub fn on_click<F>(self, on_click: F) -> Self
-    where
-        F: 'a + Fn(MouseClick) -> Option<Message>,
-    {
-        VideoPlayer {
-            on_click: Some(Box::new(on_click)),
-            ..self
+    /// Sets the message produced when a [`MouseClick`] is received.
+    pub fn on_click<F>(self, on_click: F) -> Self
+    where
+        F: 'a + Fn(MouseClick) -> Option<Message>,
+    {
+        VideoPlayer {
+            on_click: Some(Box::new(on_click)),
+            ..self
+        }
+    }
+
+    /// How far the video has faded in, see [`VideoPlayer::fade_in`].
+    fn fade(&self, tree: &widget::Tree) -> f32 {
+        let first_frame_at = tree.state.downcast_ref::<State>().first_frame_at;
+        match (self.fade_in, first_frame_at) {
+            (Some(duration), Some(at)) if !duration.is_zero() => {
+                (at.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0)
+            }
+            (Some(_), None) => 0.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Draws an image in place of the video, fitted within `bounds` like the video.
+    fn draw_poster(&self, renderer: &mut Renderer, poster: &img::Handle, bounds: iced::Rectangle) {
+        let Some(size) = renderer.measure_image(poster) else {
+            return;
+        };
+        let region = self.visible_region((size.width as i32, size.height as i32), bounds);
+        draw_handle(
+            renderer,
+            poster.clone(),
+            region,
+            Orientation::default(),
+            self.opacity,
+        );
+    }
+
+    /// Draws the frame (and inset) as a wgpu primitive if the renderer supports
+    /// it, or as images otherwise.
+    fn draw_video(
+        &self,
+        renderer: &mut Renderer,
+        tree: &widget::Tree,
+        inner: &Internal,
+        region: VisibleRegion,
+    ) {
+        if renderer.supports_primitives() {
+            self.draw_video_primitive(renderer, tree, inner, region);
+        } else {
+            self.draw_video_image(renderer, tree, inner, region);
+        }
+    }
+
+    /// Draws the frame (and inset) as a wgpu primitive.
+    fn draw_video_primitive(
+        &self,
+        renderer: &mut Renderer,
+        tree: &widget::Tree,
+        inner: &Internal,
+        region: VisibleRegion,
+    ) {
+        let mut primitive = VideoPrimitive::new(
+            inner.id,
+            Arc::clone(&inner.resident),
+            Arc::clone(&inner.frame),
+            (inner.width as _, inner.height as _),
+            inner.frame_version.load(Ordering::SeqCst),
+            inner.orientation,
+            Arc::clone(&inner.output_range),
+        )
+        .with_crop(region.source)
+        .with_effect(inner.effect)
+        .with_lut(inner.lut.clone());
+
+        if self.opacity < 1.0 || self.fade_in.is_some() {
+            primitive = primitive.with_opacity(self.opacity, self.fade(tree));
+        }
+
+        if let Some(shader) = &self.shader {
+            primitive = primitive.with_shader(shader.clone());
+        }
+
+        if let Some((video, inset)) = &self.inset
+            && !std::ptr::eq(*video, self.video)
+        {
+            let inset_inner = video.read();
+            if (inset_inner.has_video || inset_inner.cover_art.is_some())
+                && !inset_inner.awaiting_approval.load(Ordering::SeqCst)
+                && inset_inner.suspended.is_none()
+            {
+                let bounds = inset.fit(region.bounds, inset_inner.display_size());
+
+                // the frame counts as presented, see `LateFramePolicy`
+                inset_inner.upload_frame.take();
+                primitive = primitive.with_inset(InsetPrimitive {
+                    video: VideoPrimitive::new(
+                        inset_inner.id,
+                        Arc::clone(&inset_inner.resident),
+                        Arc::clone(&inset_inner.frame),
+                        (inset_inner.width as _, inset_inner.height as _),
+                        inset_inner.frame_version.load(Ordering::SeqCst),
+                        inset_inner.orientation,
+                        Arc::clone(&inset_inner.output_range),
+                    )
+                    .with_opacity(self.opacity, 1.0)
+                    .with_effect(inset_inner.effect)
+                    .with_lut(inset_inner.lut.clone()),
+                    bounds,
+                    border_width: inset.border_width,
+                    border_color: inset.border_color,
+                });
+            }
+        }
+
+        renderer.draw_video_primitive(region.bounds, primitive);
+    }
+
+    /// Draws the frame (and inset) as images converted on the CPU.
+    ///
+    /// Effects, LUTs, custom shaders and flipped orientations are not applied.
+    fn draw_video_image(
+        &self,
+        renderer: &mut Renderer,
+        tree: &widget::Tree,
+        inner: &Internal,
+        region: VisibleRegion,
+    ) {
+        let opacity = self.opacity * self.fade(tree);
+        draw_image(renderer, inner, region, opacity);
+
+        if let Some((video, inset)) = &self.inset
+            && !std::ptr::eq(*video, self.video)
+        {
+            let inset_inner = video.read();
+            if (inset_inner.has_video || inset_inner.cover_art.is_some())
+                && !inset_inner.awaiting_approval.load(Ordering::SeqCst)
+                && inset_inner.suspended.is_none()
+            {
+                let bounds = inset.fit(region.bounds, inset_inner.display_size());
+
+                // the frame counts as presented, see `LateFramePolicy`
+                inset_inner.upload_frame.take();
+                // images are drawn above quads, so the border needs its own layer
+                renderer.with_layer(region.bounds, |renderer| {
+                    if inset.border_width > 0.0 {
+                        renderer.fill_quad(
+                            advanced::renderer::Quad {
+                                bounds: bounds.expand(inset.border_width),
+                                ..Default::default()
+                            },
+                            inset.border_color.scale_alpha(self.opacity),
+                        );
+                    }
+                    renderer.with_layer(region.bounds, |renderer| {
+                        draw_image(
+                            renderer,
+                            &inset_inner,
+                            VisibleRegion {
+                                bounds,
+                                source: iced::Rectangle::new(
+                                    Point::ORIGIN,
+                                    iced::Size::new(1.0, 1.0),
+                                ),
+                            },
+                            self.opacity,
+                        );
+                    });
+                });
+            }
+        }
+    }
+
+    /// Where a video of `size` is drawn within `bounds`, and which part of it is shown.
+    fn visible_region(
+        &self,
+        (width, height): (i32, i32),
+        bounds: iced::Rectangle,
+    ) -> VisibleRegion {
+        // based on `Image::draw`
+        let image_size = iced::Size::new(width as f32, height as f32);
+        let adjusted_fit = self.content_fit.fit(image_size, bounds.size());
+        let scale = iced::Vector::new(
+            adjusted_fit.width / image_size.width,
+            adjusted_fit.height / image_size.height,
+        );
+        let final_size = image_size * scale;
+
+        let position = match self.content_fit {
+            iced::ContentFit::None => iced::Point::new(
+                bounds.x + (image_size.width - adjusted_fit.width) / 2.0,
+                bounds.y + (image_size.height - adjusted_fit.height) / 2.0,
+            ),
+            _ => iced::Point::new(
+                bounds.center_x() - final_size.width / 2.0,
+                bounds.center_y() - final_size.height / 2.0,
+            ),
+        };
+        let drawing_bounds = iced::Rectangle::new(position, final_size);
+
+        // edges overflowing the bounds (e.g., with `ContentFit::Cover`) are cropped by the shader
+        match drawing_bounds.intersection(&bounds) {
+            Some(visible) if drawing_bounds.width > 0.0 && drawing_bounds.height > 0.0 => {
+                VisibleRegion {
+                    bounds: visible,
+                    source: iced::Rectangle {
+                        x: (visible.x - drawing_bounds.x) / drawing_bounds.width,
+                        y: (visible.y - drawing_bounds.y) / drawing_bounds.height,
+                        width: visible.width / drawing_bounds.width,
+                        height: visible.height / drawing_bounds.height,
+                    },
+                }
+            }
+            _ => VisibleRegion {
+                bounds: iced::Rectangle::new(bounds.center(), iced::Size::ZERO),
+                source: iced::Rectangle::new(Point::ORIGIN, iced::Size::ZERO),
+            },
         }
     }
 }
@@ -148,7 +909,7 @@ impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
     for VideoPlayer<'_, Message, Theme, Renderer>
 where
     Message: Clone,
-    Renderer: PrimitiveRenderer,
+    Renderer: VideoRenderer,
 {
     fn size(&self) -> iced::Size<iced::Length> {
         iced::Size {
@@ -171,7 +932,7 @@ where
         _renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        let (video_width, video_height) = self.video.size();
+        let (video_width, video_height) = self.video.read().display_size();
 
         // based on `Image::layout`
         let image_size = iced::Size::new(video_width as f32, video_height as f32);
@@ -193,7 +954,7 @@ where
 
     fn draw(
         &self,
-        _tree: &widget::Tree,
+        tree: &widget::Tree,
         renderer: &mut Renderer,
         _theme: &Theme,
         _style: &advanced::renderer::Style,
@@ -203,30 +964,21 @@ where
     ) {
         let mut inner = self.video.write();
 
-        // bounds based on `Image::draw`
-        let image_size = iced::Size::new(inner.width as f32, inner.height as f32);
         let bounds = layout.bounds();
-        let adjusted_fit = self.content_fit.fit(image_size, bounds.size());
-        let scale = iced::Vector::new(
-            adjusted_fit.width / image_size.width,
-            adjusted_fit.height / image_size.height,
-        );
-        let final_size = image_size * scale;
-
-        let position = match self.content_fit {
-            iced::ContentFit::None => iced::Point::new(
-                bounds.x + (image_size.width - adjusted_fit.width) / 2.0,
-                bounds.y + (image_size.height - adjusted_fit.height) / 2.0,
-            ),
-            _ => iced::Point::new(
-                bounds.center_x() - final_size.width / 2.0,
-                bounds.center_y() - final_size.height / 2.0,
-            ),
-        };
+        let region = self.visible_region(inner.display_size(), bounds);
 
-        let drawing_bounds = iced::Rectangle::new(position, final_size);
+        // the video is drawn on top, as primitives are drawn above quads
+        if let Some(background) = self.background {
+            renderer.fill_quad(
+                advanced::renderer::Quad {
+                    bounds,
+                    ..Default::default()
+                },
+                background,
+            );
+        }
 
-        let upload_frame = inner.upload_frame.swap(false, Ordering::SeqCst);
+        let upload_frame = inner.upload_frame.take();
 
         if upload_frame {
             let last_frame_time = inner
@@ -235,25 +987,82 @@ where
                 .map(|time| *time)
                 .unwrap_or_else(|_| Instant::now());
             inner.set_av_offset(Instant::now() - last_frame_time);
+            if inner.low_latency.load(Ordering::SeqCst) {
+                inner.update_latency_estimate();
+            }
         }
 
-        let render = |renderer: &mut Renderer| {
-            renderer.draw_primitive(
-                drawing_bounds,
-                VideoPrimitive::new(
-                    inner.id,
-                    Arc::clone(&inner.alive),
-                    Arc::clone(&inner.frame),
-                    (inner.width as _, inner.height as _),
-                    upload_frame,
-                ),
-            );
-        };
+        // gated media stays hidden until approved
+        if inner.awaiting_approval.load(Ordering::SeqCst) {
+            return;
+        }
 
-        if adjusted_fit.width > bounds.width || adjusted_fit.height > bounds.height {
-            renderer.with_layer(bounds, render);
-        } else {
-            render(renderer);
+        // suspended media has no frame to show until resumed
+        if inner.suspended.is_some() {
+            if let Some(poster) = &self.poster {
+                self.draw_poster(renderer, poster, bounds);
+            }
+            return;
+        }
+
+        let ended = inner.is_eos;
+        match (&self.eos_behavior, &self.poster) {
+            (EosBehavior::Black, _) if ended => {
+                renderer.fill_quad(
+                    advanced::renderer::Quad {
+                        bounds: region.bounds,
+                        ..Default::default()
+                    },
+                    iced::Color::BLACK.scale_alpha(self.opacity),
+                );
+            }
+            (EosBehavior::Poster(poster), _) if ended => {
+                self.draw_poster(renderer, poster, bounds);
+            }
+            // nothing was decoded yet
+            (_, Some(poster)) if inner.frame_version.load(Ordering::SeqCst) == 0 => {
+                self.draw_poster(renderer, poster, bounds);
+            }
+            // audio-only media has no frames to draw, except for its cover art
+            _ if inner.has_video || inner.cover_art.is_some() => {
+                self.draw_video(renderer, tree, &inner, region);
+            }
+            _ => {}
+        }
+
+        if self.buffering_indicator
+            && matches!(
+                inner.status(),
+                PlaybackStatus::Buffering | PlaybackStatus::Stalled
+            )
+        {
+            // primitives are drawn above quads, so the spinner needs its own layer
+            renderer.with_layer(bounds, |renderer| {
+                draw_spinner(renderer, region.bounds);
+            });
+        }
+
+        let state = tree.state.downcast_ref::<State>();
+        if let Some(Drag {
+            target: Some(target),
+            ..
+        }) = state.drag
+            && !inner.duration.is_zero()
+        {
+            let progress = (target.as_secs_f64() / inner.duration.as_secs_f64()) as f32;
+            renderer.with_layer(bounds, |renderer| {
+                draw_drag_progress(renderer, bounds, progress);
+            });
+        }
+
+        if let Some(opacity) = state
+            .volume_popup_until
+            .and_then(|until| popup_opacity(until, Instant::now()))
+        {
+            let volume: f64 = inner.source.property("volume");
+            renderer.with_layer(bounds, |renderer| {
+                draw_volume_popup(renderer, bounds, volume, opacity * self.opacity);
+            });
         }
     }
 
@@ -294,7 +1103,16 @@ where
                     state.last_click,
                 );
 
-                if let Some(on_click) = &self.on_click {
+                let double_click = (*button == Button::Left && click.kind() == Kind::Double)
+                    .then(|| self.on_double_click.clone())
+                    .flatten();
+
+                if let Some(on_double_click) = double_click {
+                    // the single click which started it must not fire either
+                    state.pending_click = None;
+                    shell.publish(on_double_click);
+                    shell.capture_event();
+                } else if let Some(on_click) = &self.on_click {
                     let action = MouseAction::Button {
                         button: *button,
                         kind: click.kind(),
@@ -303,23 +1121,186 @@ where
                         modifiers: state.modifiers,
                         action,
                     };
-                    if let Some(message) = (on_click)(mouse_click) {
-                        shell.publish(message);
-                        shell.capture_event();
+
+                    // a double click supersedes the single click still waiting on it
+                    state.pending_click = None;
+
+                    // single clicks must wait out the double click interval to be told apart
+                    let click_delay = self.click_delay.or(self
+                        .on_double_click
+                        .is_some()
+                        .then_some(DOUBLE_CLICK_INTERVAL));
+
+                    match click_delay {
+                        Some(delay) if click.kind() == Kind::Single => {
+                            let at = Instant::now() + delay;
+                            state.pending_click = Some((mouse_click, at));
+                            shell.request_redraw_at(window::RedrawRequest::At(at));
+                            shell.capture_event();
+                        }
+                        _ => {
+                            if let Some(message) = (on_click)(mouse_click) {
+                                shell.publish(message);
+                                shell.capture_event();
+                            }
+                        }
                     }
                 }
 
+                if self.drag_seek && *button == Button::Left && !self.video.is_live() {
+                    state.drag = Some(Drag {
+                        origin: click.position().x,
+                        position: self.video.position(),
+                        target: None,
+                    });
+                }
+
                 state.last_click = Some(click);
                 state.last_update = Some(Update {
                     time: Instant::now(),
                     parent: Some(click.position()),
                 })
             }
+            Event::Touch(touch::Event::FingerPressed { id, position })
+                if self.touch_gestures && layout.bounds().contains(*position) =>
+            {
+                let state = state.state.downcast_mut::<State>();
+                state.touch = Some(Touch {
+                    finger: *id,
+                    origin: *position,
+                    time: Instant::now(),
+                    previous_speed: None,
+                });
+                shell.request_redraw_at(window::RedrawRequest::At(
+                    Instant::now() + LONG_PRESS_DURATION,
+                ));
+                shell.capture_event();
+            }
+            Event::Touch(touch::Event::FingerMoved { id, position }) => {
+                let state = state.state.downcast_mut::<State>();
+                // moving the finger turns a tap into something else, e.g., scrolling
+                if let Some(touch) = state.touch
+                    && touch.finger == *id
+                    && touch.previous_speed.is_none()
+                    && touch.origin.distance(*position) > DRAG_THRESHOLD
+                {
+                    state.touch = None;
+                }
+            }
+            Event::Touch(touch::Event::FingerLifted { id, position })
+            | Event::Touch(touch::Event::FingerLost { id, position }) => {
+                let state = state.state.downcast_mut::<State>();
+                let Some(touch) = state.touch.take_if(|touch| touch.finger == *id) else {
+                    return;
+                };
+                shell.capture_event();
+
+                if let Some(speed) = touch.previous_speed {
+                    if let Err(err) = self.video.write().set_speed(speed) {
+                        error!("cannot restore speed: {err}");
+                    }
+                    return;
+                }
+                if matches!(event, Event::Touch(touch::Event::FingerLost { .. })) {
+                    return;
+                }
+
+                let bounds = layout.bounds();
+                let double_tap = state.last_tap.is_some_and(|(time, last)| {
+                    time.elapsed() < DOUBLE_CLICK_INTERVAL
+                        && last.distance(*position) < DOUBLE_TAP_DISTANCE
+                });
+
+                if double_tap {
+                    state.last_tap = None;
+                    state.pending_click = None;
+
+                    let mut inner = self.video.write();
+                    let position_now = inner
+                        .source
+                        .query_position::<gst::ClockTime>()
+                        .map_or(0.0, |position| position.seconds_f64());
+                    let step = if position.x < bounds.center_x() {
+                        -DOUBLE_TAP_SEEK.as_secs_f64()
+                    } else {
+                        DOUBLE_TAP_SEEK.as_secs_f64()
+                    };
+                    let target = (position_now + step).clamp(0.0, inner.duration.as_secs_f64());
+                    if let Err(err) = inner.seek(Duration::from_secs_f64(target), false) {
+                        error!("cannot seek: {err}");
+                    }
+                } else {
+                    state.last_tap = Some((Instant::now(), *position));
+                    // taps wait for a possible double tap, like deferred clicks
+                    let at = Instant::now() + DOUBLE_CLICK_INTERVAL;
+                    state.pending_click = Some((
+                        MouseClick {
+                            modifiers: state.modifiers,
+                            action: MouseAction::Button {
+                                button: Button::Left,
+                                kind: Kind::Single,
+                            },
+                        },
+                        at,
+                    ));
+                    shell.request_redraw_at(window::RedrawRequest::At(at));
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(Button::Left)) => {
+                let state = state.state.downcast_mut::<State>();
+                if let Some(Drag {
+                    target: Some(target),
+                    ..
+                }) = state.drag.take()
+                {
+                    if let Err(err) = self.video.write().end_scrub(target) {
+                        error!("cannot seek: {err}");
+                    }
+                    shell.capture_event();
+                }
+            }
             Event::Mouse(mouse::Event::WheelScrolled { delta })
                 if cursor.is_over(layout.bounds()) =>
             {
                 let state = state.state.downcast_mut::<State>();
-                if let Some(on_click) = &self.on_click {
+                let lines = match *delta {
+                    ScrollDelta::Lines { y, .. } => y,
+                    ScrollDelta::Pixels { y, .. } => y / PIXELS_PER_LINE,
+                };
+
+                if self.wheel_behavior != WheelBehavior::None {
+                    let mut inner = self.video.write();
+                    match self.wheel_behavior {
+                        WheelBehavior::Volume => {
+                            let volume: f64 = inner.source.property("volume");
+                            let volume = (volume + lines as f64 * VOLUME_STEP).clamp(0.0, 1.0);
+                            // gstreamer unmutes when changing volume
+                            let muted: bool = inner.source.property("mute");
+                            inner.source.set_property("volume", volume);
+                            inner.source.set_property("mute", muted);
+
+                            let until = Instant::now() + VOLUME_POPUP_DURATION;
+                            state.volume_popup_until = Some(until);
+                            shell.request_redraw_at(window::RedrawRequest::At(
+                                until - POPUP_FADE_DURATION,
+                            ));
+                        }
+                        WheelBehavior::Seek => {
+                            let position = inner
+                                .source
+                                .query_position::<gst::ClockTime>()
+                                .map_or(0.0, |position| position.seconds_f64());
+                            let position = (position + lines as f64 * SEEK_STEP.as_secs_f64())
+                                .clamp(0.0, inner.duration.as_secs_f64());
+                            if let Err(err) = inner.seek(Duration::from_secs_f64(position), false) {
+                                error!("cannot seek: {err}");
+                            }
+                        }
+                        WheelBehavior::None => {}
+                    }
+                    shell.request_redraw();
+                    shell.capture_event();
+                } else if let Some(on_click) = &self.on_click {
                     let action = MouseAction::Scroll(*delta);
 
                     let mouse_click = MouseClick {
@@ -342,6 +1323,30 @@ where
             | Event::Mouse(mouse::Event::CursorLeft)
             | Event::Mouse(mouse::Event::CursorEntered) => {
                 let state = state.state.downcast_mut::<State>();
+
+                if let Some(drag) = &mut state.drag
+                    && let Some(cursor) = cursor.position()
+                {
+                    let offset = cursor.x - drag.origin;
+                    // small movements are still clicks
+                    if drag.target.is_some() || offset.abs() >= DRAG_THRESHOLD {
+                        let mut inner = self.video.write();
+                        let duration = inner.duration.as_secs_f64();
+                        let target = (drag.position.as_secs_f64()
+                            + (offset / layout.bounds().width) as f64 * duration)
+                            .clamp(0.0, duration);
+                        let target = Duration::from_secs_f64(target);
+
+                        drag.target = Some(target);
+                        state.pending_click = None;
+                        if let Err(err) = inner.scrub(target) {
+                            error!("cannot scrub: {err}");
+                        }
+                        shell.request_redraw();
+                        shell.capture_event();
+                    }
+                }
+
                 state.last_update = match state.last_update {
                     Some(Update { time, .. }) => Some(Update {
                         time,
@@ -360,62 +1365,176 @@ where
                 };
             }
             Event::Window(window::Event::RedrawRequested(_)) => {
+                let state = state.state.downcast_mut::<State>();
                 let mut inner = self.video.write();
-                if inner.restart_stream || (!inner.is_eos && !inner.paused()) {
-                    let mut restart_stream = false;
-                    if inner.restart_stream {
-                        restart_stream = true;
-                        // Set flag to false to avoid potentially multiple seeks
-                        inner.restart_stream = false;
+
+                // the end of the stream and errors are handled by the worker thread
+                let unreported = std::mem::take(&mut inner.unreported);
+                for err in &unreported.errors {
+                    if let Some(ref on_error) = self.on_error {
+                        shell.publish(on_error(err));
                     }
-                    let mut eos_pause = false;
+                }
+                if unreported.ended
+                    && let Some(on_end_of_stream) = self.on_end_of_stream.clone()
+                {
+                    shell.publish(on_end_of_stream);
+                }
+                let reached_end = unreported.ended;
+                let restarted = unreported.restarted;
 
-                    while let Some(msg) = inner
-                        .bus
-                        .pop_filtered(&[gst::MessageType::Error, gst::MessageType::Eos])
-                    {
+                // levels and missing plugins are posted whether or not the media plays
+                while let Some(msg) = inner.bus.pop_filtered(&[gst::MessageType::Element]) {
+                    let gst::MessageView::Element(element) = msg.view() else {
+                        continue;
+                    };
+                    if let Some(level) = element.structure().and_then(AudioLevel::from_structure) {
+                        let silence = inner
+                            .silence
+                            .as_mut()
+                            .and_then(|silence| silence.update(&level));
+                        match silence {
+                            Some(Silence::Start(position)) => {
+                                if let Some(ref on_silence_start) = self.on_silence_start {
+                                    shell.publish(on_silence_start(position));
+                                }
+                            }
+                            Some(Silence::End(position)) => {
+                                if let Some(ref on_silence_end) = self.on_silence_end {
+                                    shell.publish(on_silence_end(position));
+                                }
+                            }
+                            None => {}
+                        }
+                        if let Some(ref on_audio_level) = self.on_audio_level {
+                            shell.publish(on_audio_level(level));
+                        }
+                    }
+                    if let Some(missing) = MissingPlugin::from_message(&msg) {
+                        error!("missing plugin: {}", missing.description);
+                        if let Some(ref on_missing_plugin) = self.on_missing_plugin {
+                            shell.publish(on_missing_plugin(&missing));
+                        }
+                    }
+                }
+
+                if inner.restart_stream || (!inner.is_eos && !inner.paused()) {
+                    let mut frames_dropped = 0;
+
+                    while let Some(msg) = inner.bus.pop_filtered(&[
+                        gst::MessageType::ClockLost,
+                        gst::MessageType::Latency,
+                        gst::MessageType::Buffering,
+                        gst::MessageType::Toc,
+                        gst::MessageType::Qos,
+                        gst::MessageType::Tag,
+                        gst::MessageType::StreamCollection,
+                        gst::MessageType::StreamsSelected,
+                        gst::MessageType::DurationChanged,
+                    ]) {
                         match msg.view() {
-                            gst::MessageView::Error(err) => {
-                                error!("bus returned an error: {err}");
-                                if let Some(ref on_error) = self.on_error {
-                                    shell.publish(on_error(&err.error()))
-                                };
+                            gst::MessageView::ClockLost(_) => {
+                                // the clock can only be reselected by going through PAUSED
+                                let _ = inner.source.set_state(gst::State::Paused);
+                                let _ = inner.source.set_state(gst::State::Playing);
+                                if let Some(on_clock_lost) = self.on_clock_lost.clone() {
+                                    shell.publish(on_clock_lost);
+                                }
+                            }
+                            gst::MessageView::Latency(_) => {
+                                if let Err(err) = inner.source.recalculate_latency() {
+                                    error!("cannot recalculate latency: {err}");
+                                }
+                                if let Some(on_latency) = self.on_latency.clone() {
+                                    shell.publish(on_latency);
+                                }
+                            }
+                            gst::MessageView::Toc(toc) => {
+                                inner.chapters = crate::video::toc_chapters(&toc.toc().0);
                             }
-                            gst::MessageView::Eos(_eos) => {
-                                if let Some(on_end_of_stream) = self.on_end_of_stream.clone() {
-                                    shell.publish(on_end_of_stream);
+                            gst::MessageView::Tag(tag) => {
+                                if let Some(orientation) =
+                                    crate::Orientation::from_tags(&tag.tags())
+                                    && orientation != inner.orientation
+                                {
+                                    inner.orientation = orientation;
+                                    shell.invalidate_layout();
                                 }
-                                if inner.looping {
-                                    restart_stream = true;
-                                } else {
-                                    eos_pause = true;
+                            }
+                            gst::MessageView::StreamCollection(collection) => {
+                                inner.streams = Some(collection.stream_collection());
+                            }
+                            gst::MessageView::StreamsSelected(selected) => {
+                                inner.selected_streams = crate::video::stream_ids(selected);
+                            }
+                            gst::MessageView::DurationChanged(_) => {
+                                // the new duration has to be queried
+                                if let Some(duration) =
+                                    inner.source.query_duration::<gst::ClockTime>()
+                                {
+                                    let duration = Duration::from_nanos(duration.nseconds());
+                                    if duration != inner.duration {
+                                        inner.duration = duration;
+                                        if let Some(on_duration_changed) = &self.on_duration_changed
+                                        {
+                                            shell.publish(on_duration_changed(duration));
+                                        }
+                                    }
                                 }
                             }
+                            gst::MessageView::Qos(_) => {
+                                // posted for each buffer dropped by the sink or skipped by a decoder
+                                frames_dropped += 1;
+                            }
+                            gst::MessageView::Buffering(buffering) => {
+                                inner.buffering = buffering.percent() < 100;
+                            }
                             _ => {}
                         }
                     }
 
-                    // Don't run eos_pause if restart_stream is true; fixes "pausing" after restarting a stream
-                    if restart_stream {
-                        if let Err(err) = inner.restart_stream() {
-                            error!("cannot restart stream (can't seek): {err:#?}");
-                        }
-                    } else if eos_pause {
-                        inner.is_eos = true;
-                        inner.set_paused(true);
+                    if frames_dropped > 0
+                        && let Some(ref on_frames_dropped) = self.on_frames_dropped
+                    {
+                        shell.publish(on_frames_dropped(frames_dropped));
                     }
 
-                    if inner.upload_frame.load(Ordering::SeqCst) {
+                    let frame_version = inner.frame_version.load(Ordering::SeqCst);
+                    if state.frame_version != frame_version {
+                        state.frame_version = frame_version;
                         if let Some(on_new_frame) = self.on_new_frame.clone() {
                             shell.publish(on_new_frame);
                         }
+                        if let Some(on_frame_info) = &self.on_frame_info
+                            && let Some(info) = inner.frame_info()
+                        {
+                            shell.publish(on_frame_info(info));
+                        }
                     }
 
-                    if let Some(on_subtitle_text) = &self.on_subtitle_text {
-                        if inner.upload_text.swap(false, Ordering::SeqCst) {
-                            if let Ok(text) = inner.subtitle_text.try_lock() {
-                                shell.publish(on_subtitle_text(text.clone()));
+                    if (self.on_subtitle_text.is_some()
+                        || self.on_styled_subtitle.is_some()
+                        || self.on_subtitle_cue.is_some())
+                        && inner.upload_text.swap(false, Ordering::SeqCst)
+                        && let Ok(cue) = inner.subtitle_cue.try_lock()
+                    {
+                        if let Some(on_subtitle_cue) = &self.on_subtitle_cue {
+                            shell.publish(on_subtitle_cue(cue.clone()));
+                        }
+                        let styled = cue.as_ref().map(|cue| {
+                            if inner.subtitle_is_ass.load(Ordering::SeqCst) {
+                                StyledSubtitle::parse_ass(&cue.text)
+                            } else {
+                                StyledSubtitle::plain(&cue.text)
                             }
+                        });
+                        if let Some(on_subtitle_text) = &self.on_subtitle_text {
+                            shell.publish(on_subtitle_text(
+                                styled.as_ref().map(StyledSubtitle::plain_text),
+                            ));
+                        }
+                        if let Some(on_styled_subtitle) = &self.on_styled_subtitle {
+                            shell.publish(on_styled_subtitle(styled));
                         }
                     }
 
@@ -426,14 +1545,168 @@ where
                     ));
                 }
 
-                let state = state.state.downcast_mut::<State>();
+                let awaiting_approval = inner.awaiting_approval.load(Ordering::SeqCst);
+                if awaiting_approval
+                    && !state.awaiting_approval
+                    && let Some(on_approval_required) = self.on_approval_required.clone()
+                {
+                    shell.publish(on_approval_required);
+                }
+                state.awaiting_approval = awaiting_approval;
+
+                if let Some(touch) = &mut state.touch
+                    && touch.previous_speed.is_none()
+                    && touch.time.elapsed() >= LONG_PRESS_DURATION
+                {
+                    touch.previous_speed = Some(inner.speed);
+                    if let Err(err) = inner.set_speed(LONG_PRESS_SPEED) {
+                        error!("cannot change speed: {err}");
+                    }
+                }
+
+                if let Some((click, at)) = state.pending_click
+                    && Instant::now() >= at
+                {
+                    state.pending_click = None;
+                    if let Some(message) =
+                        self.on_click.as_ref().and_then(|on_click| on_click(click))
+                    {
+                        shell.publish(message);
+                    }
+                }
+
+                let status = inner.status();
+                if state.status != Some(status) {
+                    state.status = Some(status);
+                    if let Some(on_state_changed) = &self.on_state_changed {
+                        shell.publish(on_state_changed(status));
+                    }
+                }
+
+                let scene_changes = inner
+                    .scene_detector
+                    .lock()
+                    .ok()
+                    .and_then(|mut detector| {
+                        detector
+                            .as_mut()
+                            .map(|detector| std::mem::take(&mut detector.changes))
+                    })
+                    .unwrap_or_default();
+                if let Some(on_scene_change) = &self.on_scene_change {
+                    for position in scene_changes {
+                        shell.publish(on_scene_change(position));
+                    }
+                }
+
+                if let Some((interval, on_position)) = &self.on_position
+                    && !inner.paused()
+                    && !inner.is_eos
+                    && state
+                        .last_position
+                        .is_none_or(|last| last.elapsed() >= *interval)
+                    && let Some(position) = inner.source.query_position::<gst::ClockTime>()
+                {
+                    state.last_position = Some(Instant::now());
+                    shell.publish(on_position(Duration::from_nanos(position.nseconds())));
+                }
+
+                if let Some((interval, on_stream_health)) = &self.on_stream_health
+                    && state
+                        .last_stream_health
+                        .is_none_or(|last| last.elapsed() >= *interval)
+                {
+                    state.last_stream_health = Some(Instant::now());
+                    if let Some(health) = health::collect(&inner.source) {
+                        shell.publish(on_stream_health(health));
+                    }
+                }
+
+                if let Some((milestones, on_milestone)) = &self.on_milestone
+                    && !inner.is_live
+                {
+                    if restarted {
+                        state.milestones_reached.clear();
+                    }
+
+                    // milestones only need a coarse position, so don't query it every frame
+                    let progress = if reached_end {
+                        Some(1.0)
+                    } else if state
+                        .last_milestone_check
+                        .is_none_or(|last| last.elapsed() >= Duration::from_millis(250))
+                    {
+                        state.last_milestone_check = Some(Instant::now());
+                        inner
+                            .source
+                            .query_position::<gst::ClockTime>()
+                            .filter(|_| !inner.duration.is_zero())
+                            .map(|position| {
+                                position.nseconds() as f64 / inner.duration.as_nanos() as f64
+                            })
+                    } else {
+                        None
+                    };
+
+                    if let Some(progress) = progress {
+                        for &milestone in milestones {
+                            if progress >= milestone
+                                && !state.milestones_reached.contains(&milestone)
+                            {
+                                state.milestones_reached.push(milestone);
+                                shell.publish(on_milestone(milestone));
+                            }
+                        }
+                    }
+                }
+
+                // frames are redrawn at least every 32 ms, which animates the fade in
+                if state.first_frame_at.is_none() && inner.frame_version.load(Ordering::SeqCst) > 0
+                {
+                    state.first_frame_at = Some(Instant::now());
+                }
+
+                if inner.sync_size() {
+                    shell.invalidate_layout();
+                    if let Some(on_resolution_changed) = &self.on_resolution_changed {
+                        shell.publish(on_resolution_changed((inner.width, inner.height)));
+                    }
+                }
+
+                if let Some(on_visible_region_changed) = &self.on_visible_region_changed {
+                    let region = self.visible_region(inner.display_size(), layout.bounds());
+                    if state.visible_region != Some(region) {
+                        state.visible_region = Some(region);
+                        shell.publish(on_visible_region_changed(region));
+                    }
+                }
+
+                let output_range = DynamicRange::load(&inner.output_range);
+                if output_range.is_some() && output_range != state.output_range {
+                    state.output_range = output_range;
+                    if let Some((on_output_range_changed, output_range)) =
+                        self.on_output_range_changed.as_ref().zip(output_range)
+                    {
+                        shell.publish(on_output_range_changed(output_range));
+                    }
+                }
+
+                // the popup fades out frame by frame, even while paused
+                if state
+                    .volume_popup_until
+                    .and_then(|until| popup_opacity(until, Instant::now()))
+                    .is_some_and(|opacity| opacity < 1.0)
+                {
+                    shell.request_redraw_at(window::RedrawRequest::NextFrame);
+                }
+
                 match state.last_update.take() {
                     Some(Update {
                         parent: position,
                         time,
                     }) if position.is_some() => {
                         if cursor.position_over(layout.bounds()) == position
-                            && Instant::now().duration_since(time).as_secs() >= 3
+                            && time.elapsed() >= self.idle_timeout
                         {
                         } else {
                             state.last_update = Some(Update {
@@ -477,16 +1750,208 @@ impl<'a, Message, Theme, Renderer> From<VideoPlayer<'a, Message, Theme, Renderer
 where
     Message: 'a + Clone,
     Theme: 'a,
-    Renderer: 'a + PrimitiveRenderer,
+    Renderer: 'a + VideoRenderer,
 {
     fn from(video_player: VideoPlayer<'a, Message, Theme, Renderer>) -> Self {
         Self::new(video_player)
     }
 }
 
+/// What scrolling the mouse wheel over a [`VideoPlayer`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WheelBehavior {
+    /// Nothing; scrolls are passed on to [`VideoPlayer::on_click`].
+    #[default]
+    None,
+    /// Scrolling up raises the volume and scrolling down lowers it, showing
+    /// the volume over the video.
+    Volume,
+    /// Scrolling up seeks forward and scrolling down seeks backward.
+    Seek,
+}
+
+/// What a [`VideoPlayer`] shows once the media ended.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum EosBehavior {
+    /// The last frame stays on screen.
+    #[default]
+    HoldLastFrame,
+    /// The video is replaced by black.
+    Black,
+    /// The video is replaced by an image, fitted like the video.
+    Poster(img::Handle),
+}
+
+/// Approximate height of a scrolled line, for precise scrolling devices.
+const PIXELS_PER_LINE: f32 = 50.0;
+/// How much the volume changes per scrolled line.
+const VOLUME_STEP: f64 = 0.05;
+/// How far a scrolled line seeks.
+const SEEK_STEP: Duration = Duration::from_secs(5);
+/// How long the volume stays visible after scrolling, including its fade out.
+const VOLUME_POPUP_DURATION: Duration = Duration::from_secs(1);
+/// How long popups take to fade out before they are hidden.
+const POPUP_FADE_DURATION: Duration = Duration::from_millis(200);
+
+/// Get the opacity at `now` of a popup shown until `until`, or `None` once it is hidden.
+fn popup_opacity(until: Instant, now: Instant) -> Option<f32> {
+    let remaining = until.checked_duration_since(now)?;
+    Some((remaining.as_secs_f32() / POPUP_FADE_DURATION.as_secs_f32()).min(1.0))
+}
+
+/// Draws the volume as a horizontal bar near the top of the player.
+fn draw_volume_popup<Renderer: advanced::Renderer>(
+    renderer: &mut Renderer,
+    bounds: iced::Rectangle,
+    volume: f64,
+    opacity: f32,
+) {
+    const WIDTH: f32 = 120.0;
+    const HEIGHT: f32 = 6.0;
+    const PADDING: f32 = 8.0;
+
+    let background = iced::Rectangle {
+        x: bounds.center_x() - WIDTH / 2.0 - PADDING,
+        y: bounds.y + 24.0,
+        width: WIDTH + PADDING * 2.0,
+        height: HEIGHT + PADDING * 2.0,
+    };
+    renderer.fill_quad(
+        advanced::renderer::Quad {
+            bounds: background,
+            border: iced::border::rounded(PADDING),
+            ..Default::default()
+        },
+        iced::Color::BLACK.scale_alpha(0.6 * opacity),
+    );
+
+    let track = iced::Rectangle {
+        x: background.x + PADDING,
+        y: background.y + PADDING,
+        width: WIDTH,
+        height: HEIGHT,
+    };
+    renderer.fill_quad(
+        advanced::renderer::Quad {
+            bounds: track,
+            border: iced::border::rounded(HEIGHT / 2.0),
+            ..Default::default()
+        },
+        iced::Color::WHITE.scale_alpha(0.3 * opacity),
+    );
+    renderer.fill_quad(
+        advanced::renderer::Quad {
+            bounds: iced::Rectangle {
+                width: WIDTH * volume.clamp(0.0, 1.0) as f32,
+                ..track
+            },
+            border: iced::border::rounded(HEIGHT / 2.0),
+            ..Default::default()
+        },
+        iced::Color::WHITE.scale_alpha(opacity),
+    );
+}
+
+/// How long a finger must be held down to play at [`LONG_PRESS_SPEED`].
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// The speed playback runs at during a long press.
+const LONG_PRESS_SPEED: f64 = 2.0;
+/// How far apart two taps may be to count as a double tap.
+const DOUBLE_TAP_DISTANCE: f32 = 40.0;
+/// How far a double tap seeks.
+const DOUBLE_TAP_SEEK: Duration = Duration::from_secs(10);
+
+/// How far the cursor must move before a press becomes a drag.
+const DRAG_THRESHOLD: f32 = 8.0;
+
+/// Draws the target of a drag seek as a progress bar along the bottom of the player.
+fn draw_drag_progress<Renderer: advanced::Renderer>(
+    renderer: &mut Renderer,
+    bounds: iced::Rectangle,
+    progress: f32,
+) {
+    const HEIGHT: f32 = 4.0;
+
+    let track = iced::Rectangle {
+        y: bounds.y + bounds.height - HEIGHT,
+        height: HEIGHT,
+        ..bounds
+    };
+    renderer.fill_quad(
+        advanced::renderer::Quad {
+            bounds: track,
+            ..Default::default()
+        },
+        iced::Color::WHITE.scale_alpha(0.3),
+    );
+    renderer.fill_quad(
+        advanced::renderer::Quad {
+            bounds: iced::Rectangle {
+                width: track.width * progress.clamp(0.0, 1.0),
+                ..track
+            },
+            ..Default::default()
+        },
+        iced::Color::WHITE,
+    );
+}
+
+/// The interval in which iced recognizes two clicks as a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Draws a ring of dots with a bright head rotating once per second, which is
+/// animated by the redraws requested while the video is playing.
+fn draw_spinner<Renderer: advanced::Renderer>(renderer: &mut Renderer, bounds: iced::Rectangle) {
+    const DOTS: usize = 8;
+    const RING_RADIUS: f32 = 16.0;
+    const DOT_RADIUS: f32 = 4.0;
+
+    let phase = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |time| time.as_secs_f32().fract());
+    let head = (phase * DOTS as f32) as usize;
+    let center = bounds.center();
+
+    for i in 0..DOTS {
+        let angle = i as f32 / DOTS as f32 * std::f32::consts::TAU;
+        // dots fade out behind the head
+        let age = (head + DOTS - i) % DOTS;
+        let alpha = 1.0 - age as f32 / DOTS as f32;
+
+        renderer.fill_quad(
+            advanced::renderer::Quad {
+                bounds: iced::Rectangle {
+                    x: center.x + RING_RADIUS * angle.cos() - DOT_RADIUS,
+                    y: center.y + RING_RADIUS * angle.sin() - DOT_RADIUS,
+                    width: DOT_RADIUS * 2.0,
+                    height: DOT_RADIUS * 2.0,
+                },
+                border: iced::border::rounded(DOT_RADIUS),
+                ..Default::default()
+            },
+            iced::Color::WHITE.scale_alpha(alpha),
+        );
+    }
+}
+
 pub(crate) struct State {
     last_click: Option<mouse::Click>,
+    pending_click: Option<(MouseClick, Instant)>,
+    volume_popup_until: Option<Instant>,
+    drag: Option<Drag>,
+    touch: Option<Touch>,
+    last_tap: Option<(Instant, Point)>,
     modifiers: keyboard::Modifiers,
+    output_range: Option<DynamicRange>,
+    awaiting_approval: bool,
+    frame_version: u64,
+    visible_region: Option<VisibleRegion>,
+    first_frame_at: Option<Instant>,
+    last_position: Option<Instant>,
+    last_stream_health: Option<Instant>,
+    status: Option<PlaybackStatus>,
+    milestones_reached: Vec<f64>,
+    last_milestone_check: Option<Instant>,
     pub(crate) last_update: Option<Update>,
 }
 
@@ -495,17 +1960,167 @@ impl State {
         Self {
             modifiers: keyboard::Modifiers::default(),
             last_click: None,
+            pending_click: None,
+            volume_popup_until: None,
+            drag: None,
+            touch: None,
+            last_tap: None,
+            output_range: None,
+            awaiting_approval: false,
+            frame_version: 0,
+            visible_region: None,
+            first_frame_at: None,
+            last_position: None,
+            last_stream_health: None,
+            status: None,
+            milestones_reached: Vec::new(),
+            last_milestone_check: None,
             last_update: None,
         }
     }
 }
 
+/// A drag seek in progress, see [`VideoPlayer::drag_seek`].
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    origin: f32,
+    position: Duration,
+    /// Where the drag seeks to, once the cursor moved far enough.
+    target: Option<Duration>,
+}
+
+/// A finger held down on the player, see [`VideoPlayer::touch_gestures`].
+#[derive(Debug, Clone, Copy)]
+struct Touch {
+    finger: touch::Finger,
+    origin: Point,
+    time: Instant,
+    /// The speed to restore once a long press ends.
+    previous_speed: Option<f64>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct Update {
     pub time: Instant,
     pub parent: Option<Point>,
 }
 
+/// The part of the window a [`VideoPlayer`] draws its video in, see
+/// [`VideoPlayer::on_visible_region_changed`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibleRegion {
+    /// The area the video is drawn in, in logical pixels. It is smaller than the
+    /// player when letterboxed, e.g., with `ContentFit::Contain`.
+    pub bounds: iced::Rectangle,
+    /// The part of the frame shown in `bounds`, as fractions (`0.0..=1.0`) of
+    /// the displayed frame. It is smaller than the frame when the edges are
+    /// cropped, e.g., with `ContentFit::Cover`.
+    pub source: iced::Rectangle,
+}
+
+/// Draws the latest frame of a video as an image, cropped to `region`.
+fn draw_image<Renderer: VideoRenderer>(
+    renderer: &mut Renderer,
+    inner: &Internal,
+    region: VisibleRegion,
+    opacity: f32,
+) {
+    if let Some(handle) = inner.frame_image() {
+        draw_handle(renderer, handle, region, inner.orientation, opacity);
+    }
+}
+
+/// Draws an image in `orientation`, cropped to `region`.
+fn draw_handle<Renderer: VideoRenderer>(
+    renderer: &mut Renderer,
+    handle: img::Handle,
+    region: VisibleRegion,
+    orientation: Orientation,
+    opacity: f32,
+) {
+    // the whole image is drawn, clipped to the visible part of it
+    let width = region.bounds.width / region.source.width;
+    let height = region.bounds.height / region.source.height;
+    let frame = iced::Rectangle {
+        x: region.bounds.x - region.source.x * width,
+        y: region.bounds.y - region.source.y * height,
+        width,
+        height,
+    };
+
+    // images are rotated about their center, with the bounds of the unrotated image
+    let turns = orientation.shader_value() % 4;
+    let frame = if orientation.is_transposed() {
+        let center = frame.center();
+        iced::Rectangle::new(
+            Point::new(center.x - height / 2.0, center.y - width / 2.0),
+            iced::Size::new(height, width),
+        )
+    } else {
+        frame
+    };
+
+    renderer.draw_image(
+        advanced::image::Image {
+            handle,
+            filter_method: advanced::image::FilterMethod::Linear,
+            rotation: iced::Radians(turns as f32 * f32::consts::FRAC_PI_2),
+            opacity,
+            snap: true,
+        },
+        frame,
+        region.bounds,
+    );
+}
+
+/// Placement of a secondary video composited by [`VideoPlayer::picture_in_picture`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Inset {
+    /// The area the inset is fitted into, as fractions (`0.0..=1.0`) of the
+    /// area of the primary video. The aspect ratio of the inset is preserved.
+    pub bounds: iced::Rectangle,
+    /// The width of the border drawn around the inset, in logical pixels.
+    pub border_width: f32,
+    /// The color of the border drawn around the inset.
+    pub border_color: iced::Color,
+}
+
+impl Inset {
+    /// Fits a video of `size` into the inset area of the primary video's `bounds`.
+    fn fit(&self, bounds: iced::Rectangle, (width, height): (i32, i32)) -> iced::Rectangle {
+        let area = iced::Rectangle {
+            x: bounds.x + self.bounds.x * bounds.width,
+            y: bounds.y + self.bounds.y * bounds.height,
+            width: self.bounds.width * bounds.width,
+            height: self.bounds.height * bounds.height,
+        };
+        let size = iced::Size::new(width as f32, height as f32);
+        let size = iced::ContentFit::Contain.fit(size, area.size());
+        iced::Rectangle::new(
+            iced::Point::new(
+                area.center_x() - size.width / 2.0,
+                area.center_y() - size.height / 2.0,
+            ),
+            size,
+        )
+    }
+}
+
+impl Default for Inset {
+    fn default() -> Self {
+        Inset {
+            bounds: iced::Rectangle {
+                x: 0.7,
+                y: 0.7,
+                width: 0.25,
+                height: 0.25,
+            },
+            border_width: 2.0,
+            border_color: iced::Color::WHITE,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 /// A mouse click.
 pub struct MouseClick {
@@ -534,3 +2149,33 @@ pub struct KeyPress {
     // The state of keyboard modifiers.
     pub modifiers: Modifiers,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fades_out_popups() {
+        let now = Instant::now();
+        let until = now + VOLUME_POPUP_DURATION;
+        assert_eq!(popup_opacity(until, now), Some(1.0));
+        assert_eq!(popup_opacity(until, until - POPUP_FADE_DURATION), Some(1.0));
+        assert_eq!(
+            popup_opacity(until, until - POPUP_FADE_DURATION / 2),
+            Some(0.5)
+        );
+        assert_eq!(popup_opacity(until, until), Some(0.0));
+        assert_eq!(popup_opacity(until, until + Duration::from_millis(1)), None);
+    }
+
+    #[test]
+    fn restarts_popup_fade_when_shown_again() {
+        let now = Instant::now();
+        let until = now + VOLUME_POPUP_DURATION;
+        // scrolling again mid-fade pushes the popup's end back to full opacity
+        let fading = until - POPUP_FADE_DURATION / 2;
+        assert_eq!(popup_opacity(until, fading), Some(0.5));
+        let until = fading + VOLUME_POPUP_DURATION;
+        assert_eq!(popup_opacity(until, fading), Some(1.0));
+    }
+}